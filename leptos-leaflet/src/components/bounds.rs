@@ -32,6 +32,27 @@ impl Bounds {
         }
     }
 
+    /// Creates the bounds with two opposite corners, in any order.
+    pub(crate) fn from_corners(a: Position, b: Position) -> Self {
+        Self {
+            ne_corner: Position::new(a.lat.max(b.lat), a.lng.max(b.lng)),
+            sw_corner: Position::new(a.lat.min(b.lat), a.lng.min(b.lng)),
+        }
+    }
+
+    /// Creates new bounds from planar coordinates, as used by `Crs::Simple`.
+    ///
+    /// # Arguments
+    ///
+    /// - `min`: The `(x, y)` corner with the lowest coordinates.
+    /// - `max`: The `(x, y)` corner with the highest coordinates.
+    pub fn from_xy(min: (f64, f64), max: (f64, f64)) -> Self {
+        Self {
+            ne_corner: Position::from_xy(max.0, max.1),
+            sw_corner: Position::from_xy(min.0, min.1),
+        }
+    }
+
     /// Gets the center of the bounds.
     pub fn get_center(&self) -> Position {
         Position {
@@ -81,6 +102,18 @@ impl Bounds {
             && self.ne_corner.lng >= position.lng
     }
 
+    /// Returns the closest position inside the bounds, like a dragged marker kept in an area.
+    ///
+    /// # Arguments
+    ///
+    /// - `position`: The position to move inside the bounds.
+    pub fn clamp(&self, position: Position) -> Position {
+        Position {
+            lat: position.lat.max(self.sw_corner.lat).min(self.ne_corner.lat),
+            lng: position.lng.max(self.sw_corner.lng).min(self.ne_corner.lng),
+        }
+    }
+
     /// Returns true if the rectangle intersects the given bounds.
     /// Two bounds intersect if they have at least one point in common.
     ///
@@ -111,7 +144,9 @@ impl Bounds {
         lat_overlap && lng_overlap
     }
 
-    /// Returns true if the bounds are valid.
+    /// Returns true if the bounds are valid geographic bounds.
+    ///
+    /// For planar bounds under `Crs::Simple` use [`Bounds::is_ordered`] instead.
     pub fn is_valid(&self) -> bool {
         self.ne_corner.lat <= 90.0
             && self.ne_corner.lat >= -90.0
@@ -125,6 +160,11 @@ impl Bounds {
             && self.ne_corner.lng >= self.sw_corner.lng
     }
 
+    /// Returns true if the northeast corner is above and to the right of the southwest corner.
+    pub fn is_ordered(&self) -> bool {
+        self.ne_corner.lat >= self.sw_corner.lat && self.ne_corner.lng >= self.sw_corner.lng
+    }
+
     /// Returns a new bounds padded by the given ratio.
     pub fn pad(&self, buffer_ratio: f64) -> Bounds {
         let lat_diff = self.ne_corner.lat - self.sw_corner.lat;
@@ -142,6 +182,11 @@ impl Bounds {
         self.ne_corner == other.ne_corner && self.sw_corner == other.sw_corner
     }
 
+    /// Checks if two bounds are equal within a small tolerance.
+    pub(crate) fn approx_eq(&self, other: &Self) -> bool {
+        self.ne_corner.approx_eq(&other.ne_corner) && self.sw_corner.approx_eq(&other.sw_corner)
+    }
+
     pub fn as_lat_lng_bounds(&self) -> LatLngBounds {
         LatLngBounds::new(
             &LatLng::new(self.ne_corner.lat, self.ne_corner.lng),
@@ -167,3 +212,21 @@ impl From<&Bounds> for LatLngBounds {
         )
     }
 }
+
+impl From<LatLngBounds> for Bounds {
+    fn from(value: LatLngBounds) -> Self {
+        Bounds::new(
+            value.get_north_east().into(),
+            value.get_south_west().into(),
+        )
+    }
+}
+
+impl From<&LatLngBounds> for Bounds {
+    fn from(value: &LatLngBounds) -> Self {
+        Bounds::new(
+            value.get_north_east().into(),
+            value.get_south_west().into(),
+        )
+    }
+}
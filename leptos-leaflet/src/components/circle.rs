@@ -1,8 +1,9 @@
 use leaflet::CircleOptions;
 use leptos::prelude::*;
 
+use super::shape_editor::edit_circle;
 use super::{
-    extend_context_with_overlay, use_pane_context, FillRule, LayerEvents, LeafletMapContext,
+    extend_context_with_overlay, use_layer_parent, use_pane_context, FillRule, LayerEvents,
     LineCap, LineJoin, MouseEvents, MoveEvents, PaneRendererScope, PopupEvents, Position,
     StringEmptyOption, TooltipEvents,
 };
@@ -16,9 +17,14 @@ use tracing::debug;
 ///
 /// The `Circle` component is used to create a circle overlay on the map. It provides options to customize
 /// the appearance of the circle, such as the stroke color, fill color, and radius.
+///
+/// With `editable_center` and `editable_radius`, handles let the user move and resize the
+/// circle, written to the signals which the circle then follows.
 #[component(transparent)]
 pub fn Circle(
-    #[prop(into)] center: JsSignal<Position>,
+    /// Center of the circle.
+    #[prop(into, optional)]
+    center: JsSignal<Position>,
     #[prop(into, optional)] stroke: Signal<Option<bool>>,
     #[prop(into, optional)] color: Signal<String>,
     #[prop(into, optional)] weight: Signal<Option<f64>>,
@@ -40,20 +46,38 @@ pub fn Circle(
     #[prop(into, optional)] tooltip_events: TooltipEvents,
     #[prop(into, optional)] move_events: MoveEvents,
 
-    #[prop(into)] radius: Signal<f64>,
+    /// Radius of the circle, in meters.
+    #[prop(into, optional)]
+    radius: Signal<f64>,
+    /// Center moved with a handle on the map. Takes precedence over `center`, which is ignored
+    /// when both are set.
+    #[prop(optional)]
+    editable_center: Option<RwSignal<Position>>,
+    /// Radius changed with a handle on the edge of the circle. Takes precedence over `radius`,
+    /// which is ignored when both are set.
+    #[prop(optional)]
+    editable_radius: Option<RwSignal<f64>>,
+    /// Shows the edit handles of an editable shape.
+    #[prop(into, optional, default = true.into())]
+    editing: Signal<bool>,
     #[prop(optional)] children: Option<Children>,
 ) -> impl IntoView {
+    let center = editable_center.map_or(center, |editable_center| {
+        Signal::derive_local(move || editable_center.get())
+    });
+    let radius = editable_radius.map_or(radius, Signal::from);
     let position_tracking = center;
     let overlay_context = extend_context_with_overlay();
+    let layer_parent = use_layer_parent();
     let overlay = JsStoredValue::new_local(None::<leaflet::Circle>);
+    if editable_center.is_some() || editable_radius.is_some() {
+        edit_circle(center, radius, editable_center, editable_radius, editing);
+    }
 
     let color_clone = color;
     let fill_color_clone = fill_color;
     Effect::new(move |_| {
-        if let Some(map) = use_context::<LeafletMapContext>()
-            .expect("map context")
-            .map()
-        {
+        if layer_parent.is_ready() {
             let options = CircleOptions::new();
             setup_layer_leaflet_option!(stroke, options);
             setup_layer_leaflet_string!(color, options);
@@ -138,7 +162,7 @@ pub fn Circle(
             layer_events.setup(&circle);
             move_events.setup(&circle);
 
-            circle.add_to(&map);
+            layer_parent.add_layer(&circle);
             overlay_context.set_container(&circle);
             overlay.set_value(Some(circle));
         };
@@ -248,7 +272,7 @@ pub fn Circle(
         fill_opacity_stop.stop();
         weight_stop.stop();
         if let Some(overlay) = overlay.try_get_value().flatten().as_ref() {
-            overlay.remove();
+            layer_parent.remove_layer(&overlay);
         }
     });
 
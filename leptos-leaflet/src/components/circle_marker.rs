@@ -0,0 +1,232 @@
+use leaflet::CircleOptions;
+use leptos::prelude::*;
+
+use super::{
+    extend_context_with_overlay, use_layer_parent, use_pane_context, FillRule, LayerEvents,
+    LineCap, LineJoin, MouseEvents, MoveEvents, PaneRendererScope, PopupEvents, Position,
+    StringEmptyOption, TooltipEvents,
+};
+use crate::{
+    core::{JsSignal, JsStoredValue},
+    setup_layer_leaflet_option, setup_layer_leaflet_option_ref, setup_layer_leaflet_string,
+};
+
+/// A circle of a fixed size in pixels, that keeps its size on screen at any zoom.
+///
+/// Unlike [`Circle`](super::Circle), whose radius is in meters, this is meant for data points.
+/// To render thousands of them, put them in a `Pane` with a canvas renderer:
+///
+/// ```rust,no_run
+/// use leptos::prelude::*;
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn Points(points: Vec<Position>) -> impl IntoView {
+///     view! {
+///         <Pane name="points" renderer=PaneRendererScope::PaneSpecificCanvas>
+///             {points
+///                 .into_iter()
+///                 .map(|point| view! { <CircleMarker center=point radius=4.0 /> })
+///                 .collect_view()}
+///         </Pane>
+///     }
+/// }
+/// ```
+#[component(transparent)]
+pub fn CircleMarker(
+    #[prop(into)] center: JsSignal<Position>,
+    /// Radius of the circle, in pixels. Defaults to 10.0
+    #[prop(into, optional, default = 10.0.into())]
+    radius: Signal<f64>,
+    #[prop(into, optional)] stroke: Signal<Option<bool>>,
+    #[prop(into, optional)] color: Signal<String>,
+    #[prop(into, optional)] weight: Signal<Option<f64>>,
+    #[prop(into, optional)] interactive: Signal<Option<bool>>,
+    #[prop(into, optional)] opacity: Signal<Option<f64>>,
+    #[prop(into, optional)] line_cap: Signal<Option<LineCap>>,
+    #[prop(into, optional)] line_join: Signal<Option<LineJoin>>,
+    #[prop(into, optional)] dash_array: Signal<String>,
+    #[prop(into, optional)] dash_offset: Signal<String>,
+    #[prop(into, optional)] fill: Signal<Option<bool>>,
+    #[prop(into, optional)] fill_color: Signal<String>,
+    #[prop(into, optional)] fill_opacity: Signal<Option<f64>>,
+    #[prop(into, optional)] fill_rule: Signal<Option<FillRule>>,
+    #[prop(into, optional)] bubbling_mouse_events: Signal<Option<bool>>,
+    #[prop(into, optional)] class_name: Signal<String>,
+    #[prop(into, optional)] mouse_events: MouseEvents,
+    #[prop(into, optional)] layer_events: LayerEvents,
+    #[prop(into, optional)] popup_events: PopupEvents,
+    #[prop(into, optional)] tooltip_events: TooltipEvents,
+    #[prop(into, optional)] move_events: MoveEvents,
+    #[prop(optional)] children: Option<Children>,
+) -> impl IntoView {
+    let overlay_context = extend_context_with_overlay();
+    let layer_parent = use_layer_parent();
+    let overlay = JsStoredValue::new_local(None::<leaflet::CircleMarker>);
+
+    Effect::new(move |_| {
+        if layer_parent.is_ready() {
+            let options = CircleOptions::new();
+            options.set_radius(radius.get_untracked());
+            setup_layer_leaflet_option!(stroke, options);
+            setup_layer_leaflet_string!(color, options);
+            setup_layer_leaflet_option!(weight, options);
+            setup_layer_leaflet_option!(opacity, options);
+            setup_layer_leaflet_option!(interactive, options);
+            setup_layer_leaflet_option_ref!(line_cap, options);
+            setup_layer_leaflet_option_ref!(line_join, options);
+            setup_layer_leaflet_string!(dash_array, options);
+            setup_layer_leaflet_string!(dash_offset, options);
+            setup_layer_leaflet_option!(fill, options);
+            setup_layer_leaflet_string!(fill_color, options);
+            setup_layer_leaflet_option!(fill_opacity, options);
+            setup_layer_leaflet_option_ref!(fill_rule, options);
+            setup_layer_leaflet_option!(bubbling_mouse_events, options);
+            setup_layer_leaflet_string!(class_name, options);
+
+            // Set pane and renderer if available from pane context
+            if let Some(pane_context) = use_pane_context() {
+                options.set_pane(pane_context.name().to_string());
+                match pane_context.renderer_scope() {
+                    PaneRendererScope::PaneSpecificSvg => {
+                        if let Some(renderer) = pane_context.svg_renderer() {
+                            options.set_renderer(renderer.into());
+                        }
+                    }
+                    PaneRendererScope::PaneSpecificCanvas => {
+                        if let Some(renderer) = pane_context.canvas_renderer() {
+                            options.set_renderer(renderer.into());
+                        }
+                    }
+                    PaneRendererScope::Global => {}
+                }
+            }
+
+            let circle_marker = leaflet::CircleMarker::new_with_options(
+                &center.get_untracked().as_lat_lng(),
+                &options,
+            );
+
+            mouse_events.setup(&circle_marker);
+            popup_events.setup(&circle_marker);
+            tooltip_events.setup(&circle_marker);
+            layer_events.setup(&circle_marker);
+            move_events.setup(&circle_marker);
+
+            layer_parent.add_layer(&circle_marker);
+            overlay_context.set_container(&circle_marker);
+            overlay.set_value(Some(circle_marker));
+        };
+    });
+
+    let position_stop = Effect::watch(
+        move || center.get(),
+        move |center, _, _| {
+            if let Some(circle_marker) = overlay.get_value().as_ref() {
+                circle_marker.set_lat_lng(&center.as_lat_lng());
+            }
+        },
+        false,
+    );
+
+    let radius_stop = Effect::watch(
+        move || radius.get(),
+        move |radius, _, _| {
+            if let Some(circle_marker) = overlay.get_value().as_ref() {
+                circle_marker.set_radius(*radius);
+            }
+        },
+        false,
+    );
+
+    // A single watcher for the whole style keeps the number of effects low with many markers,
+    // only the fields that changed are sent to Leaflet.
+    let style_stop = Effect::watch(
+        move || CircleMarkerStyle {
+            stroke: stroke.get(),
+            color: color.get(),
+            weight: weight.get(),
+            opacity: opacity.get(),
+            fill: fill.get(),
+            fill_color: fill_color.get(),
+            fill_opacity: fill_opacity.get(),
+        },
+        move |style, previous, _| {
+            let Some(circle_marker) = overlay.get_value() else {
+                return;
+            };
+            let options = CircleOptions::new();
+            if style.apply_changes(previous, &options) {
+                circle_marker.set_style(&options);
+            }
+        },
+        false,
+    );
+
+    on_cleanup(move || {
+        position_stop.stop();
+        radius_stop.stop();
+        style_stop.stop();
+        if let Some(overlay) = overlay.try_get_value().flatten().as_ref() {
+            layer_parent.remove_layer(overlay);
+        }
+    });
+
+    children.map(|child| child())
+}
+
+/// Default stroke color of Leaflet paths.
+const DEFAULT_COLOR: &str = "#3388ff";
+
+/// The reactive style of a [`CircleMarker`].
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CircleMarkerStyle {
+    stroke: Option<bool>,
+    color: String,
+    weight: Option<f64>,
+    opacity: Option<f64>,
+    fill: Option<bool>,
+    fill_color: String,
+    fill_opacity: Option<f64>,
+}
+
+impl CircleMarkerStyle {
+    /// Sets the fields that changed since `previous` in `options`, returns false if none did.
+    ///
+    /// Fields that went back to unset are reset to the Leaflet defaults of a circle marker.
+    fn apply_changes(&self, previous: Option<&Self>, options: &CircleOptions) -> bool {
+        let previous = previous.cloned().unwrap_or_default();
+        let mut changed = false;
+        if self.stroke != previous.stroke {
+            options.set_stroke(self.stroke.unwrap_or(true));
+            changed = true;
+        }
+        if self.color != previous.color {
+            let color = self.color.to_option().map(String::as_str);
+            options.set_color(color.unwrap_or(DEFAULT_COLOR).to_string());
+            changed = true;
+        }
+        if self.weight != previous.weight {
+            options.set_weight(self.weight.unwrap_or(3.0));
+            changed = true;
+        }
+        if self.opacity != previous.opacity {
+            options.set_opacity(self.opacity.unwrap_or(1.0));
+            changed = true;
+        }
+        if self.fill != previous.fill {
+            options.set_fill(self.fill.unwrap_or(true));
+            changed = true;
+        }
+        if self.fill_color != previous.fill_color {
+            // An empty fill color makes Leaflet use the stroke color
+            options.set_fill_color(self.fill_color.clone());
+            changed = true;
+        }
+        if self.fill_opacity != previous.fill_opacity {
+            options.set_fill_opacity(self.fill_opacity.unwrap_or(0.2));
+            changed = true;
+        }
+        changed
+    }
+}
@@ -11,6 +11,7 @@ use crate::core::{JsReadSignal, JsRwSignal, JsWriteSignal};
 #[derive(Debug, Clone, Copy)]
 pub struct LeafletMapContext {
     map: JsRwSignal<Option<leaflet::Map>>,
+    double_click_zoom_suspended: RwSignal<bool>,
     thread_id: std::thread::ThreadId,
 }
 
@@ -19,6 +20,7 @@ impl LeafletMapContext {
     pub fn new() -> Self {
         Self {
             map: JsRwSignal::new_local(None),
+            double_click_zoom_suspended: RwSignal::new(false),
             thread_id: std::thread::current().id(),
         }
     }
@@ -96,6 +98,20 @@ impl LeafletMapContext {
         layer.remove_from(&map);
     }
 
+    /// Keeps the double click zoom of the map disabled while set, for tools using double clicks.
+    ///
+    /// Once resumed, the `double_click_zoom` of the `MapContainer` applies again.
+    pub(crate) fn suspend_double_click_zoom(&self, suspended: bool) {
+        if self.double_click_zoom_suspended.try_get_untracked() != Some(suspended) {
+            self.double_click_zoom_suspended.try_set(suspended);
+        }
+    }
+
+    /// Wether the double click zoom is suspended, this is tracked.
+    pub(crate) fn double_click_zoom_suspended(&self) -> bool {
+        self.double_click_zoom_suspended.get()
+    }
+
     fn is_valid(&self) -> bool {
         std::thread::current().id() == self.thread_id && !self.map.is_disposed()
     }
@@ -253,3 +269,137 @@ pub type MapWriteSignal = JsWriteSignal<Option<Map>>;
 pub fn create_map_signal() -> (MapReadSignal, MapWriteSignal) {
     JsRwSignal::new_local(None).split()
 }
+
+/// A context struct for layer groups.
+///
+/// The layer components inside a `LayerGroup` or a `FeatureGroup` add their layers to the group
+/// instead of the map.
+#[derive(Debug, Clone, Copy)]
+pub struct LayerGroupContext {
+    group: JsRwSignal<Option<leaflet::LayerGroup>>,
+    thread_id: std::thread::ThreadId,
+}
+
+impl LayerGroupContext {
+    pub fn new() -> Self {
+        Self {
+            group: JsRwSignal::new_local(None),
+            thread_id: std::thread::current().id(),
+        }
+    }
+
+    /// Sets the group for the context.
+    pub fn set_group<G: Into<leaflet::LayerGroup> + Clone>(&self, group: &G) {
+        if !self.is_valid() {
+            leptos::logging::error!("Accessing map from a different thread. Probably running on the server.");
+            return;
+        }
+        self.group.set(Some(group.clone().into()));
+    }
+
+    /// Returns the group once it has been created.
+    pub fn group(&self) -> Option<leaflet::LayerGroup> {
+        if self.is_valid() {
+            self.group.get()
+        } else {
+            leptos::logging::error!("Accessing map from a different thread. Probably running on the server.");
+            None
+        }
+    }
+
+    pub fn group_untracked(&self) -> Option<leaflet::LayerGroup> {
+        if self.is_valid() {
+            self.group.get_untracked()
+        } else {
+            None
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        std::thread::current().id() == self.thread_id && !self.group.is_disposed()
+    }
+}
+
+impl Default for LayerGroupContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Provides a layer group context, for the children of a group component.
+pub fn provide_layer_group_context() -> LayerGroupContext {
+    let context = LayerGroupContext::new();
+    provide_context(context);
+    context
+}
+
+/// Returns the context of the closest layer group, if any.
+pub fn use_layer_group_context() -> Option<LayerGroupContext> {
+    use_context::<LayerGroupContext>()
+}
+
+/// The parent a layer component adds its layer to: the closest layer group, or the map.
+#[derive(Debug, Clone, Copy)]
+pub struct LayerParent {
+    map_context: LeafletMapContext,
+    group_context: Option<LayerGroupContext>,
+}
+
+impl LayerParent {
+    /// Returns true once layers can be added, that is when the map and the group (when inside one)
+    /// have been created.
+    ///
+    /// This is tracked, so the layers are created from effects calling it.
+    pub fn is_ready(&self) -> bool {
+        self.map_context.map().is_some()
+            && self
+                .group_context
+                .map(|group_context| group_context.group().is_some())
+                .unwrap_or(true)
+    }
+
+    /// Adds a layer to the group, or to the map.
+    pub fn add_layer<L: Into<leaflet::Layer> + Clone>(&self, layer: &L) {
+        let layer: leaflet::Layer = layer.to_owned().into();
+        match self.group() {
+            Some(group) => {
+                group.add_layer(&layer);
+            }
+            None => {
+                if let Some(map) = self.map_context.map_untracked() {
+                    layer.add_to(&map);
+                }
+            }
+        }
+    }
+
+    /// Removes a layer from the group, or from the map.
+    pub fn remove_layer<L: Into<leaflet::Layer> + Clone>(&self, layer: &L) {
+        let layer: leaflet::Layer = layer.to_owned().into();
+        match self.group() {
+            Some(group) => {
+                group.remove_layer(&layer);
+            }
+            None => {
+                layer.remove();
+            }
+        }
+    }
+
+    fn group(&self) -> Option<leaflet::LayerGroup> {
+        self.group_context
+            .and_then(|group_context| group_context.group_untracked())
+    }
+}
+
+/// Returns the parent the layer of the current component must be added to.
+///
+/// # Panics
+///
+/// Panics if called outside of a `MapContainer`.
+pub fn use_layer_parent() -> LayerParent {
+    LayerParent {
+        map_context: use_leaflet_context().expect("map context not found"),
+        group_context: use_layer_group_context(),
+    }
+}
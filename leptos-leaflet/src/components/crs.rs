@@ -0,0 +1,264 @@
+use js_sys::{Array, Object, Reflect};
+use leptos::logging::error;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::core::{get_function, leaflet_namespace, set_option};
+
+/// Coordinate reference system used by the map.
+///
+/// Under [`Crs::Simple`] positions are planar, use [`Position::from_xy`](super::Position::from_xy)
+/// and [`Bounds::from_xy`](super::Bounds::from_xy) to work with `x`/`y` coordinates.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum Crs {
+    /// Spherical Mercator, the default of Leaflet.
+    #[default]
+    Epsg3857,
+    /// Elliptical Mercator, used by some tile providers like Yandex.
+    Epsg3395,
+    /// Equirectangular projection.
+    Epsg4326,
+    /// Maps `lng`/`lat` directly to `x`/`y`, for floor plans, game maps and other flat images.
+    Simple,
+    /// A CRS derived from another one, with a custom transformation and/or zoom resolutions.
+    Custom(CustomCrs),
+    /// A proj4 CRS, requires the Proj4Leaflet plugin to be loaded.
+    Proj4(Proj4Crs),
+}
+
+/// An affine transformation `(a * x + b, c * y + d)` from projected to pixel coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transformation {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+}
+
+impl Transformation {
+    /// Creates a new transformation.
+    pub fn new(a: f64, b: f64, c: f64, d: f64) -> Self {
+        Self { a, b, c, d }
+    }
+}
+
+/// A CRS derived from a base CRS.
+///
+/// # Example
+///
+/// A flat image where `y` grows downwards and zoom 0 shows 1 pixel per unit:
+///
+/// ```rust,no_run
+/// use leptos_leaflet::prelude::*;
+///
+/// let crs = Crs::Custom(CustomCrs {
+///     transformation: Some(Transformation::new(1.0, 0.0, 1.0, 0.0)),
+///     ..CustomCrs::new(Crs::Simple)
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CustomCrs {
+    /// The CRS the projection and defaults are taken from.
+    pub base: Box<Crs>,
+    /// Code of the CRS, e.g. `"EPSG:28992"`.
+    pub code: Option<String>,
+    /// Transformation from projected to pixel coordinates.
+    pub transformation: Option<Transformation>,
+    /// Units per pixel for each zoom level, starting at zoom 0.
+    pub resolutions: Option<Vec<f64>>,
+    /// Range the longitude (`x` in planar maps) is wrapped around.
+    pub wrap_lng: Option<(f64, f64)>,
+    /// Range the latitude (`y` in planar maps) is wrapped around.
+    pub wrap_lat: Option<(f64, f64)>,
+    /// Wether the coordinate space is unbounded.
+    pub infinite: Option<bool>,
+}
+
+impl CustomCrs {
+    /// Creates a custom CRS that behaves like `base` until fields are changed.
+    pub fn new(base: Crs) -> Self {
+        Self {
+            base: Box::new(base),
+            ..Default::default()
+        }
+    }
+}
+
+/// A CRS defined by a proj4 string, built with `L.Proj.CRS` from Proj4Leaflet.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Proj4Crs {
+    /// Code of the CRS, e.g. `"EPSG:3006"`.
+    pub code: String,
+    /// The proj4 definition of the CRS.
+    pub definition: String,
+    /// Units per pixel for each zoom level, starting at zoom 0.
+    pub resolutions: Option<Vec<f64>>,
+    /// Origin of the tile grid in projected coordinates.
+    pub origin: Option<(f64, f64)>,
+}
+
+impl Crs {
+    /// Builds the Leaflet CRS object.
+    ///
+    /// Returns `None` if Leaflet (or Proj4Leaflet for [`Crs::Proj4`]) is not loaded.
+    pub fn to_leaflet(&self) -> Option<JsValue> {
+        let leaflet = leaflet_namespace()?;
+        let crs_namespace = Reflect::get(&leaflet, &JsValue::from_str("CRS")).ok()?;
+        let named = |name: &str| {
+            Reflect::get(&crs_namespace, &JsValue::from_str(name))
+                .ok()
+                .filter(|crs| !crs.is_undefined())
+        };
+        match self {
+            Crs::Epsg3857 => named("EPSG3857"),
+            Crs::Epsg3395 => named("EPSG3395"),
+            Crs::Epsg4326 => named("EPSG4326"),
+            Crs::Simple => named("Simple"),
+            Crs::Custom(custom) => custom.to_leaflet(&leaflet),
+            Crs::Proj4(proj4) => proj4.to_leaflet(&leaflet),
+        }
+    }
+}
+
+impl CustomCrs {
+    fn to_leaflet(&self, leaflet: &JsValue) -> Option<JsValue> {
+        let base = self.base.to_leaflet()?;
+        // Same as `L.Util.extend({}, base, {...})`, which is how Leaflet defines its own CRSs
+        let crs = Object::assign(&Object::new(), base.unchecked_ref());
+        if let Some(code) = &self.code {
+            set_option(&crs, "code", &JsValue::from_str(code));
+        }
+        if let Some(transformation) = self.transformation {
+            let constructor = Reflect::get(leaflet, &JsValue::from_str("Transformation")).ok()?;
+            let args = Array::of4(
+                &transformation.a.into(),
+                &transformation.b.into(),
+                &transformation.c.into(),
+                &transformation.d.into(),
+            );
+            let transformation = Reflect::construct(constructor.unchecked_ref(), &args).ok()?;
+            set_option(&crs, "transformation", &transformation);
+        }
+        if let Some(resolutions) = &self.resolutions {
+            let scale_resolutions = resolutions.clone();
+            let scale = Closure::<dyn Fn(f64) -> f64>::new(move |zoom| {
+                scale_for_zoom(&scale_resolutions, zoom)
+            });
+            let zoom_resolutions = resolutions.clone();
+            let zoom = Closure::<dyn Fn(f64) -> f64>::new(move |scale| {
+                zoom_for_scale(&zoom_resolutions, scale)
+            });
+            // The CRS lives as long as the map, so the closures are handed over to JS
+            set_option(&crs, "scale", &scale.into_js_value());
+            set_option(&crs, "zoom", &zoom.into_js_value());
+        }
+        if let Some((min, max)) = self.wrap_lng {
+            set_option(&crs, "wrapLng", &Array::of2(&min.into(), &max.into()));
+        }
+        if let Some((min, max)) = self.wrap_lat {
+            set_option(&crs, "wrapLat", &Array::of2(&min.into(), &max.into()));
+        }
+        if let Some(infinite) = self.infinite {
+            set_option(&crs, "infinite", &JsValue::from_bool(infinite));
+        }
+        Some(crs.into())
+    }
+}
+
+impl Proj4Crs {
+    fn to_leaflet(&self, leaflet: &JsValue) -> Option<JsValue> {
+        let constructor = Reflect::get(leaflet, &JsValue::from_str("Proj"))
+            .ok()
+            .and_then(|proj| get_function(&proj, "CRS"));
+        let Some(constructor) = constructor else {
+            error!("L.Proj.CRS is not available, make sure Proj4Leaflet is loaded.");
+            return None;
+        };
+        let options = Object::new();
+        if let Some(resolutions) = &self.resolutions {
+            let resolutions = resolutions
+                .iter()
+                .copied()
+                .map(JsValue::from)
+                .collect::<Array>();
+            set_option(&options, "resolutions", &resolutions);
+        }
+        if let Some((x, y)) = self.origin {
+            set_option(&options, "origin", &Array::of2(&x.into(), &y.into()));
+        }
+        let args = Array::of3(
+            &JsValue::from_str(&self.code),
+            &JsValue::from_str(&self.definition),
+            &options,
+        );
+        Reflect::construct(&constructor, &args).ok()
+    }
+}
+
+/// Scale (pixels per unit) for a zoom level, interpolating between fractional zooms.
+fn scale_for_zoom(resolutions: &[f64], zoom: f64) -> f64 {
+    if resolutions.is_empty() {
+        return 1.0;
+    }
+    let last = resolutions.len() - 1;
+    let down = (zoom.floor().max(0.0) as usize).min(last);
+    let down_scale = 1.0 / resolutions[down];
+    if down == last || zoom <= down as f64 {
+        return down_scale;
+    }
+    let up_scale = 1.0 / resolutions[down + 1];
+    down_scale + (up_scale - down_scale) * (zoom - down as f64)
+}
+
+/// Zoom level for a scale, the inverse of [`scale_for_zoom`].
+fn zoom_for_scale(resolutions: &[f64], scale: f64) -> f64 {
+    let scales = resolutions.iter().map(|resolution| 1.0 / resolution);
+    let Some((down, down_scale)) = scales
+        .enumerate()
+        .filter(|(_, candidate)| *candidate <= scale)
+        .last()
+    else {
+        return f64::NEG_INFINITY;
+    };
+    if down_scale == scale {
+        return down as f64;
+    }
+    let Some(up_scale) = resolutions.get(down + 1).map(|resolution| 1.0 / resolution) else {
+        return f64::INFINITY;
+    };
+    (scale - down_scale) / (up_scale - down_scale) + down as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESOLUTIONS: [f64; 4] = [8.0, 4.0, 2.0, 1.0];
+
+    #[test]
+    fn test_scale_for_zoom() {
+        assert_eq!(scale_for_zoom(&RESOLUTIONS, 0.0), 0.125);
+        assert_eq!(scale_for_zoom(&RESOLUTIONS, 3.0), 1.0);
+        assert_eq!(scale_for_zoom(&RESOLUTIONS, 1.5), 0.375);
+        // Out of range zooms are clamped to the known resolutions
+        assert_eq!(scale_for_zoom(&RESOLUTIONS, 10.0), 1.0);
+        assert_eq!(scale_for_zoom(&RESOLUTIONS, -1.0), 0.125);
+    }
+
+    #[test]
+    fn test_zoom_for_scale() {
+        assert_eq!(zoom_for_scale(&RESOLUTIONS, 0.125), 0.0);
+        assert_eq!(zoom_for_scale(&RESOLUTIONS, 1.0), 3.0);
+        assert_eq!(zoom_for_scale(&RESOLUTIONS, 0.375), 1.5);
+        assert_eq!(zoom_for_scale(&RESOLUTIONS, 0.01), f64::NEG_INFINITY);
+        assert_eq!(zoom_for_scale(&RESOLUTIONS, 2.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_custom_crs_defaults_to_base() {
+        let custom = CustomCrs::new(Crs::Simple);
+        assert_eq!(*custom.base, Crs::Simple);
+        assert!(custom.transformation.is_none());
+        assert!(custom.resolutions.is_none());
+    }
+}
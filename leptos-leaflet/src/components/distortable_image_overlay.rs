@@ -0,0 +1,326 @@
+use js_sys::{Array, Reflect};
+use leptos::html::Img;
+use leptos::logging::error;
+use leptos::prelude::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlImageElement;
+
+use super::{use_layer_parent, use_pane_context, Position};
+use crate::core::{
+    call_method, get_function, leaflet_namespace, JsStoredValue, LeafletEventListener,
+};
+
+/// The corners where an image is pinned on the map, see [`DistortableImageOverlay`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageCorners {
+    pub top_left: Position,
+    pub top_right: Position,
+    pub bottom_left: Position,
+    /// When `None`, the image is placed with an affine transform (a parallelogram) from the other
+    /// three corners. When set, the image is placed with a projective transform.
+    pub bottom_right: Option<Position>,
+}
+
+impl ImageCorners {
+    /// Creates the corners of an affine placement.
+    pub fn new(top_left: Position, top_right: Position, bottom_left: Position) -> Self {
+        Self {
+            top_left,
+            top_right,
+            bottom_left,
+            bottom_right: None,
+        }
+    }
+
+    /// Sets the bottom-right corner, for a projective placement.
+    pub fn with_bottom_right(mut self, bottom_right: Position) -> Self {
+        self.bottom_right = Some(bottom_right);
+        self
+    }
+}
+
+/// An image overlay placed by its corners instead of north-up bounds, like a scanned map.
+///
+/// The image is distorted with a CSS transform computed from the corners, and kept in place when
+/// the map is zoomed or panned, or when the corners change.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use leptos::prelude::*;
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn HistoricalMap() -> impl IntoView {
+///     let corners = ImageCorners::new(
+///         position!(51.52, -0.12),
+///         position!(51.53, -0.08),
+///         position!(51.50, -0.11),
+///     );
+///     view! { <DistortableImageOverlay url="/old-london.jpg" corners=corners opacity=0.7 /> }
+/// }
+/// ```
+#[component(transparent)]
+pub fn DistortableImageOverlay(
+    #[prop(into)] url: Signal<String>,
+    #[prop(into)] corners: Signal<ImageCorners>,
+    #[prop(into, optional)] opacity: Option<Signal<f64>>,
+    #[prop(into, optional)] alt: Option<Signal<String>>,
+    #[prop(into, optional)] class_name: Option<Signal<String>>,
+    #[prop(into, optional)] pane: Option<Signal<String>>,
+) -> impl IntoView {
+    let layer_parent = use_layer_parent();
+    let layer = JsStoredValue::new_local(None::<leaflet::Layer>);
+    let hooks = JsStoredValue::new_local(Vec::<Closure<dyn Fn(JsValue)>>::new());
+    let map = JsStoredValue::new_local(None::<leaflet::Map>);
+    let view_listener = JsStoredValue::new_local(None::<LeafletEventListener>);
+
+    // Render the image to an element, which is moved into the pane when the layer is added
+    let image = NodeRef::<Img>::new();
+
+    let update = move || {
+        if let (Some(map), Some(image)) = (map.get_value(), image.get_untracked()) {
+            update_transform(&map, &image, &corners.get_untracked());
+        }
+    };
+
+    Effect::new(move |_| {
+        if layer_parent.is_ready() {
+            let pane_name = pane
+                .map(|pane| pane.get_untracked())
+                .filter(|pane| !pane.is_empty())
+                .or_else(|| use_pane_context().map(|context| context.name().to_string()))
+                .unwrap_or_else(|| "overlayPane".to_string());
+
+            // A bare `L.Layer`, drawing the image in `onAdd` and removing it in `onRemove`
+            let on_add = Closure::<dyn Fn(JsValue)>::new(move |leaflet_map: JsValue| {
+                let leaflet_map = leaflet_map.unchecked_into::<leaflet::Map>();
+                let Some(element) = image.get_untracked() else {
+                    return;
+                };
+                if let Some(pane) = get_pane(&leaflet_map, &pane_name) {
+                    let _ = pane.append_child(&element);
+                }
+                view_listener.set_value(Some(LeafletEventListener::new(
+                    &leaflet_map,
+                    "zoom viewreset",
+                    move |_: leaflet::Event| update(),
+                )));
+                map.set_value(Some(leaflet_map));
+                update();
+            });
+            let on_remove = Closure::<dyn Fn(JsValue)>::new(move |_: JsValue| {
+                if let Some(element) = image.get_untracked() {
+                    element.remove();
+                }
+                view_listener.set_value(None);
+                map.set_value(None);
+            });
+            let Some(image_layer) = create_layer(&on_add, &on_remove) else {
+                return;
+            };
+            hooks.set_value(vec![on_add, on_remove]);
+            layer_parent.add_layer(&image_layer);
+            layer.set_value(Some(image_layer));
+        }
+    });
+
+    let corners_stop = Effect::watch(move || corners.get(), move |_, _, _| update(), false);
+
+    on_cleanup(move || {
+        corners_stop.stop();
+        if let Some(layer) = layer.try_get_value().flatten() {
+            layer_parent.remove_layer(&layer);
+        }
+        // After the removal, which calls the hooks
+        hooks.try_update_value(Vec::clear);
+    });
+
+    let class = move || {
+        let class_name = class_name.map(|class_name| class_name.get());
+        match class_name.as_deref() {
+            Some(class_name) if !class_name.is_empty() => {
+                format!("leaflet-image-layer leaflet-zoom-hide {class_name}")
+            }
+            _ => "leaflet-image-layer leaflet-zoom-hide".to_string(),
+        }
+    };
+
+    view! {
+        <div style="visibility:collapse">
+            <img
+                node_ref=image
+                src=move || url.get()
+                alt=move || alt.map(|alt| alt.get())
+                class=class
+                style:transform-origin="0 0"
+                style:opacity=move || opacity.map_or(1.0, |opacity| opacity.get()).to_string()
+                on:load=move |_| update()
+            />
+        </div>
+    }
+}
+
+/// Creates a `L.Layer` calling the given closures when added to and removed from a map.
+fn create_layer(
+    on_add: &Closure<dyn Fn(JsValue)>,
+    on_remove: &Closure<dyn Fn(JsValue)>,
+) -> Option<leaflet::Layer> {
+    let layer_class = get_function(&leaflet_namespace()?, "Layer")?;
+    let layer = match Reflect::construct(&layer_class, &Array::new()) {
+        Ok(layer) => layer,
+        Err(err) => {
+            error!("Failed to create image layer: {:?}", err);
+            return None;
+        }
+    };
+    Reflect::set(&layer, &JsValue::from_str("onAdd"), on_add.as_ref()).ok()?;
+    Reflect::set(&layer, &JsValue::from_str("onRemove"), on_remove.as_ref()).ok()?;
+    Some(layer.unchecked_into())
+}
+
+fn get_pane(map: &leaflet::Map, name: &str) -> Option<web_sys::HtmlElement> {
+    call_method(map, "getPane", &[JsValue::from_str(name)])?
+        .dyn_into()
+        .ok()
+}
+
+/// Places the image on its corners, in the layer pixels of the map.
+fn update_transform(map: &leaflet::Map, image: &HtmlImageElement, corners: &ImageCorners) {
+    let size = (
+        f64::from(image.natural_width()),
+        f64::from(image.natural_height()),
+    );
+    // Not loaded yet
+    if size.0 == 0.0 || size.1 == 0.0 {
+        return;
+    }
+    let layer_point = |position: &Position| {
+        let point = map.lat_lng_to_layer_point(&position.as_lat_lng());
+        (point.x(), point.y())
+    };
+    let top_left = layer_point(&corners.top_left);
+    let top_right = layer_point(&corners.top_right);
+    let bottom_left = layer_point(&corners.bottom_left);
+    let bottom_right = corners.bottom_right.as_ref().map_or(
+        (
+            top_right.0 + bottom_left.0 - top_left.0,
+            top_right.1 + bottom_left.1 - top_left.1,
+        ),
+        layer_point,
+    );
+    let style = image.style();
+    match projective_transform(size, [top_left, top_right, bottom_right, bottom_left]) {
+        Some(matrix) => {
+            let _ = style.set_property("transform", &css_matrix3d(&matrix));
+            let _ = style.remove_property("display");
+        }
+        // Corners on a line, nothing to show
+        None => {
+            let _ = style.set_property("display", "none");
+        }
+    }
+}
+
+/// Computes the projective transform mapping an image of the given size on the corners.
+///
+/// The corners are in the order top-left, top-right, bottom-right, bottom-left. The result is the
+/// 3x3 matrix in row-major order, mapping `(x, y, 1)` in image pixels to homogeneous coordinates.
+/// Returns `None` when the corners are degenerate.
+fn projective_transform(size: (f64, f64), corners: [(f64, f64); 4]) -> Option<[f64; 9]> {
+    let [(x0, y0), (x1, y1), (x2, y2), (x3, y3)] = corners;
+    let (width, height) = size;
+
+    // Maps the unit square on the corners (Heckbert), the transform is affine when the corners
+    // form a parallelogram.
+    let sx = x0 - x1 + x2 - x3;
+    let sy = y0 - y1 + y2 - y3;
+    let (dx1, dx2, dy1, dy2) = (x1 - x2, x3 - x2, y1 - y2, y3 - y2);
+    let det = dx1 * dy2 - dx2 * dy1;
+    if det.abs() < f64::EPSILON || !det.is_finite() {
+        return None;
+    }
+    let g = (sx * dy2 - dx2 * sy) / det;
+    let h = (dx1 * sy - sx * dy1) / det;
+    let a = x1 - x0 + g * x1;
+    let b = x3 - x0 + h * x3;
+    let d = y1 - y0 + g * y1;
+    let e = y3 - y0 + h * y3;
+
+    // Then scales the image down to the unit square
+    Some([
+        a / width,
+        b / height,
+        x0,
+        d / width,
+        e / height,
+        y0,
+        g / width,
+        h / height,
+        1.0,
+    ])
+}
+
+/// Formats a 3x3 projective transform as a CSS `matrix3d`, which is in column-major order.
+fn css_matrix3d(matrix: &[f64; 9]) -> String {
+    let [a, b, c, d, e, f, g, h, i] = matrix;
+    format!("matrix3d({a}, {d}, 0, {g}, {b}, {e}, 0, {h}, 0, 0, 1, 0, {c}, {f}, 0, {i})")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(matrix: &[f64; 9], (x, y): (f64, f64)) -> (f64, f64) {
+        let w = matrix[6] * x + matrix[7] * y + matrix[8];
+        (
+            (matrix[0] * x + matrix[1] * y + matrix[2]) / w,
+            (matrix[3] * x + matrix[4] * y + matrix[5]) / w,
+        )
+    }
+
+    fn assert_close((x, y): (f64, f64), (expected_x, expected_y): (f64, f64)) {
+        assert!(
+            (x - expected_x).abs() < 1e-9 && (y - expected_y).abs() < 1e-9,
+            "({x}, {y}) != ({expected_x}, {expected_y})"
+        );
+    }
+
+    #[test]
+    fn test_projective_transform_maps_corners() {
+        let size = (200.0, 100.0);
+        let corners = [(10.0, 20.0), (250.0, 5.0), (300.0, 180.0), (-20.0, 150.0)];
+        let matrix = projective_transform(size, corners).unwrap();
+
+        assert_close(project(&matrix, (0.0, 0.0)), corners[0]);
+        assert_close(project(&matrix, (200.0, 0.0)), corners[1]);
+        assert_close(project(&matrix, (200.0, 100.0)), corners[2]);
+        assert_close(project(&matrix, (0.0, 100.0)), corners[3]);
+    }
+
+    #[test]
+    fn test_parallelogram_is_affine() {
+        let corners = [(0.0, 0.0), (100.0, 50.0), (150.0, 150.0), (50.0, 100.0)];
+        let matrix = projective_transform((10.0, 10.0), corners).unwrap();
+
+        assert_eq!(matrix[6], 0.0);
+        assert_eq!(matrix[7], 0.0);
+        assert_close(project(&matrix, (5.0, 5.0)), (75.0, 75.0));
+    }
+
+    #[test]
+    fn test_degenerate_corners() {
+        let corners = [(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)];
+        assert_eq!(projective_transform((10.0, 10.0), corners), None);
+    }
+
+    #[test]
+    fn test_css_matrix3d() {
+        let identity = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0];
+        assert_eq!(
+            css_matrix3d(&identity),
+            "matrix3d(1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1)"
+        );
+    }
+}
@@ -0,0 +1,354 @@
+use leptos::prelude::*;
+
+use super::{Bounds, Position};
+
+/// A tool of the [`DrawControl`](super::DrawControl).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawTool {
+    /// Places a marker with a click.
+    Marker,
+    /// Adds a vertex on every click, clicking the last vertex again finishes the line.
+    Polyline,
+    /// Adds a vertex on every click, clicking the first or last vertex finishes the polygon.
+    Polygon,
+    /// Clicks two opposite corners.
+    Rectangle,
+    /// Clicks the center, then a point on the circle.
+    Circle,
+}
+
+impl DrawTool {
+    /// All the tools, in the order of the toolbar.
+    pub const ALL: [DrawTool; 5] = [
+        DrawTool::Marker,
+        DrawTool::Polyline,
+        DrawTool::Polygon,
+        DrawTool::Rectangle,
+        DrawTool::Circle,
+    ];
+
+    /// A short name of the tool, used as the title of its button.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DrawTool::Marker => "Marker",
+            DrawTool::Polyline => "Line",
+            DrawTool::Polygon => "Polygon",
+            DrawTool::Rectangle => "Rectangle",
+            DrawTool::Circle => "Circle",
+        }
+    }
+
+    /// The number of clicked positions needed to finish a shape with this tool.
+    fn min_positions(&self) -> usize {
+        match self {
+            DrawTool::Marker => 1,
+            DrawTool::Polyline | DrawTool::Rectangle | DrawTool::Circle => 2,
+            DrawTool::Polygon => 3,
+        }
+    }
+}
+
+/// A shape drawn with the [`DrawControl`](super::DrawControl).
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawnShape {
+    Marker(Position),
+    Polyline(Vec<Position>),
+    /// The outer ring of the polygon, without repeating the first position.
+    Polygon(Vec<Position>),
+    Rectangle(Bounds),
+    /// The center and the radius in meters, or in map units for `Crs::Simple`.
+    Circle {
+        center: Position,
+        radius: f64,
+    },
+}
+
+/// A handle to start, finish or cancel drawing, and to follow what is being drawn.
+///
+/// Create it with [`use_draw`], anywhere in the app, and pass it to the `handle` of a
+/// [`DrawControl`](super::DrawControl) to drive the control from outside of the map.
+#[derive(Debug, Clone, Copy)]
+pub struct DrawHandle {
+    pub(crate) tool: RwSignal<Option<DrawTool>>,
+    pub(crate) drawn: RwSignal<Option<DrawnShape>>,
+    pub(crate) finish_requested: Trigger,
+}
+
+impl DrawHandle {
+    /// Starts drawing with `tool`, dropping the shape being drawn if any.
+    pub fn start(&self, tool: DrawTool) {
+        self.tool.set(Some(tool));
+    }
+
+    /// Stops drawing, dropping the shape being drawn.
+    pub fn cancel(&self) {
+        self.tool.set(None);
+    }
+
+    /// Finishes the shape being drawn if it has enough positions, like pressing `Enter`.
+    pub fn finish(&self) {
+        self.finish_requested.notify();
+    }
+
+    /// The tool in use, `None` when not drawing.
+    ///
+    /// This is tracked.
+    pub fn tool(&self) -> Option<DrawTool> {
+        self.tool.get()
+    }
+
+    /// Returns true while a tool is in use.
+    ///
+    /// This is tracked.
+    pub fn is_drawing(&self) -> bool {
+        self.tool.with(Option::is_some)
+    }
+
+    /// The last finished shape.
+    ///
+    /// This is tracked.
+    pub fn last_drawn(&self) -> Option<DrawnShape> {
+        self.drawn.get()
+    }
+}
+
+/// Creates a [`DrawHandle`] for a [`DrawControl`](super::DrawControl).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use leptos::prelude::*;
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn Editor() -> impl IntoView {
+///     let draw = use_draw();
+///     let zones = RwSignal::new(Vec::<Vec<Position>>::new());
+///     let on_draw = move |shape: DrawnShape| {
+///         if let DrawnShape::Polygon(positions) = shape {
+///             zones.update(|zones| zones.push(positions));
+///         }
+///     };
+///     view! {
+///         <button on:click=move |_| draw.start(DrawTool::Polygon)>"New zone"</button>
+///         <MapContainer center=Position::new(51.505, -0.09) zoom=13.0>
+///             <TileLayer url="https://tile.openstreetmap.org/{z}/{x}/{y}.png" />
+///             <DrawControl handle=draw tools=vec![] on_draw=on_draw />
+///             <For each=move || zones.get() key=|zone| format!("{zone:?}") let:zone>
+///                 <Polygon positions=zone />
+///             </For>
+///         </MapContainer>
+///     }
+/// }
+/// ```
+pub fn use_draw() -> DrawHandle {
+    DrawHandle {
+        tool: RwSignal::new(None),
+        drawn: RwSignal::new(None),
+        finish_requested: Trigger::new(),
+    }
+}
+
+/// The positions clicked so far for a shape.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Sketch {
+    tool: DrawTool,
+    positions: Vec<Position>,
+}
+
+impl Sketch {
+    pub(crate) fn new(tool: DrawTool) -> Self {
+        Self {
+            tool,
+            positions: Vec::new(),
+        }
+    }
+
+    pub(crate) fn tool(&self) -> DrawTool {
+        self.tool
+    }
+
+    pub(crate) fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    /// The vertices that finish the shape when clicked again.
+    pub(crate) fn closing_positions(&self) -> Vec<Position> {
+        let (Some(first), Some(last)) = (self.positions.first(), self.positions.last()) else {
+            return Vec::new();
+        };
+        match self.tool {
+            DrawTool::Polyline if self.positions.len() >= 2 => vec![*last],
+            DrawTool::Polygon if self.positions.len() >= 3 => vec![*first, *last],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Adds a clicked position, returning the shape when it's finished.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The clicked position, snapped if needed.
+    /// * `closes` - Whether the click is on one of the [`Sketch::closing_positions`].
+    /// * `distance` - Measures the radius of circles.
+    pub(crate) fn click(
+        &mut self,
+        position: Position,
+        closes: bool,
+        distance: impl Fn(&Position, &Position) -> f64,
+    ) -> Option<DrawnShape> {
+        if closes {
+            return self.finish(&distance);
+        }
+        self.positions.push(position);
+        match self.tool {
+            DrawTool::Marker | DrawTool::Rectangle | DrawTool::Circle => self.finish(&distance),
+            DrawTool::Polyline | DrawTool::Polygon => None,
+        }
+    }
+
+    /// The finished shape, `None` until there are enough positions.
+    pub(crate) fn finish(
+        &self,
+        distance: impl Fn(&Position, &Position) -> f64,
+    ) -> Option<DrawnShape> {
+        if self.positions.len() < self.tool.min_positions() {
+            return None;
+        }
+        Some(shape(self.tool, &self.positions, distance))
+    }
+
+    /// The shape as it would be with the cursor at `cursor`, to show while drawing.
+    pub(crate) fn preview(
+        &self,
+        cursor: Option<Position>,
+        distance: impl Fn(&Position, &Position) -> f64,
+    ) -> Option<DrawnShape> {
+        let mut positions = self.positions.clone();
+        positions.extend(cursor);
+        match (self.tool, positions.len()) {
+            (DrawTool::Marker, _) | (_, 0 | 1) => None,
+            // Shown as a line until it has an area
+            (DrawTool::Polygon, 2) => Some(DrawnShape::Polyline(positions)),
+            (tool, _) => Some(shape(tool, &positions, distance)),
+        }
+    }
+}
+
+fn shape(
+    tool: DrawTool,
+    positions: &[Position],
+    distance: impl Fn(&Position, &Position) -> f64,
+) -> DrawnShape {
+    match tool {
+        DrawTool::Marker => DrawnShape::Marker(positions[0]),
+        DrawTool::Polyline => DrawnShape::Polyline(positions.to_vec()),
+        DrawTool::Polygon => DrawnShape::Polygon(positions.to_vec()),
+        DrawTool::Rectangle => {
+            DrawnShape::Rectangle(Bounds::from_corners(positions[0], positions[1]))
+        }
+        DrawTool::Circle => DrawnShape::Circle {
+            center: positions[0],
+            radius: distance(&positions[0], &positions[1]),
+        },
+    }
+}
+
+/// The closest of the `candidates` within `max_distance` pixels of the cursor.
+///
+/// # Arguments
+///
+/// * `cursor` - The cursor in pixels.
+/// * `candidates` - Positions with their pixel coordinates.
+/// * `max_distance` - The snapping distance in pixels.
+pub(crate) fn snap(
+    cursor: (f64, f64),
+    candidates: impl IntoIterator<Item = (Position, (f64, f64))>,
+    max_distance: f64,
+) -> Option<Position> {
+    candidates
+        .into_iter()
+        .map(|(position, (x, y))| (position, (x - cursor.0).hypot(y - cursor.1)))
+        .filter(|(_, distance)| *distance <= max_distance)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(position, _)| position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance(a: &Position, b: &Position) -> f64 {
+        a.distance(b)
+    }
+
+    #[test]
+    fn test_polygon_closes_on_first_vertex() {
+        let mut sketch = Sketch::new(DrawTool::Polygon);
+        let points = [
+            Position::new(0.0, 0.0),
+            Position::new(0.0, 1.0),
+            Position::new(1.0, 1.0),
+        ];
+        for point in points {
+            assert_eq!(sketch.click(point, false, distance), None);
+        }
+        assert_eq!(sketch.closing_positions(), vec![points[0], points[2]]);
+        assert_eq!(
+            sketch.click(points[0], true, distance),
+            Some(DrawnShape::Polygon(points.to_vec()))
+        );
+    }
+
+    #[test]
+    fn test_polyline_needs_two_vertices() {
+        let mut sketch = Sketch::new(DrawTool::Polyline);
+        sketch.click(Position::new(0.0, 0.0), false, distance);
+        assert!(sketch.closing_positions().is_empty());
+        assert_eq!(sketch.finish(distance), None);
+        sketch.click(Position::new(1.0, 0.0), false, distance);
+        assert_eq!(
+            sketch.finish(distance),
+            Some(DrawnShape::Polyline(vec![
+                Position::new(0.0, 0.0),
+                Position::new(1.0, 0.0)
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_rectangle_and_circle_finish_on_second_click() {
+        let mut rectangle = Sketch::new(DrawTool::Rectangle);
+        assert_eq!(
+            rectangle.click(Position::new(2.0, 0.0), false, distance),
+            None
+        );
+        assert_eq!(
+            rectangle.click(Position::new(0.0, 3.0), false, distance),
+            Some(DrawnShape::Rectangle(Bounds::new(
+                Position::new(2.0, 3.0),
+                Position::new(0.0, 0.0)
+            )))
+        );
+
+        let mut circle = Sketch::new(DrawTool::Circle);
+        circle.click(Position::new(0.0, 0.0), false, distance);
+        assert_eq!(
+            circle.preview(Some(Position::new(3.0, 4.0)), distance),
+            Some(DrawnShape::Circle {
+                center: Position::new(0.0, 0.0),
+                radius: 5.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_snap_to_closest_within_distance() {
+        let a = Position::new(0.0, 0.0);
+        let b = Position::new(1.0, 1.0);
+        let candidates = [(a, (10.0, 10.0)), (b, (14.0, 10.0))];
+        assert_eq!(snap((13.0, 10.0), candidates, 5.0), Some(b));
+        assert_eq!(snap((11.0, 10.0), candidates, 5.0), Some(a));
+        assert_eq!(snap((30.0, 10.0), candidates, 5.0), None);
+    }
+}
@@ -0,0 +1,426 @@
+use js_sys::Object;
+use leaflet::{to_lat_lng_array, CircleOptions, PathOptions, PolylineOptions};
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+
+use super::draw::{snap, Sketch};
+use super::{
+    use_draw, use_leaflet_context, Control, DrawHandle, DrawTool, DrawnShape, LeafletMapContext,
+    Position,
+};
+use crate::core::{call_method, DomEventListener, JsStoredValue, LeafletEventListener};
+
+/// Distance in pixels within which a click on a vertex finishes the shape.
+const CLOSE_DISTANCE: f64 = 10.0;
+
+/// A control to draw markers, lines, polygons, rectangles and circles on the map.
+///
+/// Every finished shape is passed to `on_draw` as a [`DrawnShape`], to be stored and shown by the
+/// app, the control itself only shows the shape being drawn. `Escape` cancels the shape, `Enter`
+/// finishes a line or polygon.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use leptos::prelude::*;
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn Sketchpad(stops: Signal<Vec<Position>>) -> impl IntoView {
+///     let routes = RwSignal::new(Vec::<Vec<Position>>::new());
+///     let style = PathOptions::new();
+///     style.set_color("purple".to_string());
+///     view! {
+///         <MapContainer center=Position::new(51.505, -0.09) zoom=13.0>
+///             <TileLayer url="https://tile.openstreetmap.org/{z}/{x}/{y}.png" />
+///             <DrawControl
+///                 tools=vec![DrawTool::Polyline]
+///                 path_options=style
+///                 snap_distance=12.0
+///                 snap_positions=stops
+///                 repeat=true
+///                 on_draw=move |shape: DrawnShape| {
+///                     if let DrawnShape::Polyline(route) = shape {
+///                         routes.update(|routes| routes.push(route));
+///                     }
+///                 }
+///             />
+///         </MapContainer>
+///     }
+/// }
+/// ```
+#[component]
+pub fn DrawControl(
+    /// Drives the control from outside of the map, see [`use_draw`].
+    #[prop(optional)]
+    handle: Option<DrawHandle>,
+    /// The tools of the toolbar, no toolbar is shown when empty.
+    #[prop(optional, default = DrawTool::ALL.to_vec())]
+    tools: Vec<DrawTool>,
+    /// Position of the toolbar.
+    #[prop(into, optional, default = Signal::derive(|| "topleft".to_string()))]
+    position: Signal<String>,
+    /// Style of the shape being drawn.
+    #[prop(optional)]
+    path_options: Option<PathOptions>,
+    /// Snaps clicks within this distance in pixels to the vertices of the shape being drawn and
+    /// to `snap_positions`.
+    #[prop(into, optional)]
+    snap_distance: Option<Signal<f64>>,
+    /// More positions to snap to, like the vertices of the shapes already drawn.
+    #[prop(into, optional)]
+    snap_positions: Option<Signal<Vec<Position>>>,
+    /// Keeps the tool in use after finishing a shape, to draw several in a row.
+    #[prop(optional)]
+    repeat: bool,
+    #[prop(into, optional)] on_draw: Option<Callback<DrawnShape>>,
+) -> impl IntoView {
+    let handle = handle.unwrap_or_else(use_draw);
+    let map_context =
+        use_leaflet_context().expect("DrawControl must be used within a MapContainer");
+
+    let sketch = StoredValue::new(None::<Sketch>);
+    let cursor = StoredValue::new(None::<Position>);
+    let preview = JsStoredValue::new_local(None::<PreviewLayer>);
+    let path_options = JsStoredValue::new_local(path_options);
+    let listeners = JsStoredValue::new_local(Vec::<LeafletEventListener>::new());
+    let key_listener = JsStoredValue::new_local(None::<DomEventListener>);
+
+    let update_preview = move || {
+        let Some(map) = map_context.map_untracked() else {
+            return;
+        };
+        let shape = sketch.with_value(|sketch| {
+            sketch.as_ref().and_then(|sketch| {
+                sketch.preview(cursor.get_value(), |a, b| map_distance(&map, a, b))
+            })
+        });
+        preview.update_value(|preview| {
+            // The layer is only created again when the kind of shape changes
+            if let (Some(layer), Some(shape)) = (preview.as_ref(), shape.as_ref()) {
+                if layer.update(shape) {
+                    return;
+                }
+            }
+            if let Some(previous) = preview.take() {
+                previous.layer().remove();
+            }
+            *preview = shape.and_then(|shape| {
+                path_options
+                    .with_value(|path_options| PreviewLayer::new(&shape, path_options.as_ref()))
+            });
+            if let Some(layer) = preview {
+                layer.layer().add_to(&map);
+            }
+        });
+    };
+
+    let complete = move |shape: DrawnShape| {
+        if repeat {
+            sketch.update_value(|sketch| {
+                if let Some(sketch) = sketch {
+                    *sketch = Sketch::new(sketch.tool());
+                }
+            });
+        } else {
+            // Also dropped right away, in case of another click before the tool is updated
+            sketch.set_value(None);
+            handle.cancel();
+        }
+        update_preview();
+        handle.drawn.set(Some(shape.clone()));
+        if let Some(on_draw) = on_draw {
+            on_draw.run(shape);
+        }
+    };
+
+    // Snaps the cursor to the vertices of the shape and the `snap_positions`
+    let snapped = move |map: &leaflet::Map, position: Position| {
+        let Some(snap_distance) = snap_distance else {
+            return position;
+        };
+        let mut targets = sketch
+            .with_value(|sketch| sketch.as_ref().map(|sketch| sketch.positions().to_vec()))
+            .unwrap_or_default();
+        if let Some(snap_positions) = snap_positions {
+            targets.extend(snap_positions.get_untracked());
+        }
+        snap(
+            layer_point(map, &position),
+            targets
+                .into_iter()
+                .map(|target| (target, layer_point(map, &target))),
+            snap_distance.get_untracked(),
+        )
+        .unwrap_or(position)
+    };
+
+    let on_click = move |event: leaflet::MouseEvent| {
+        let Some(map) = map_context.map_untracked() else {
+            return;
+        };
+        let clicked = Position::from(event.lat_lng());
+        let Some(closing) =
+            sketch.with_value(|sketch| sketch.as_ref().map(Sketch::closing_positions))
+        else {
+            return;
+        };
+        let closes = snap(
+            layer_point(&map, &clicked),
+            closing
+                .into_iter()
+                .map(|vertex| (vertex, layer_point(&map, &vertex))),
+            CLOSE_DISTANCE,
+        )
+        .is_some();
+        let position = snapped(&map, clicked);
+        let shape = sketch
+            .try_update_value(|sketch| {
+                sketch.as_mut().and_then(|sketch| {
+                    sketch.click(position, closes, |a, b| map_distance(&map, a, b))
+                })
+            })
+            .flatten();
+        match shape {
+            Some(shape) => complete(shape),
+            None => update_preview(),
+        }
+    };
+
+    let on_mouse_move = move |event: leaflet::MouseEvent| {
+        let Some(map) = map_context.map_untracked() else {
+            return;
+        };
+        if sketch.with_value(Option::is_none) {
+            return;
+        }
+        cursor.set_value(Some(snapped(&map, Position::from(event.lat_lng()))));
+        update_preview();
+    };
+
+    let on_key_down = move |event: web_sys::Event| {
+        let Some(event) = event.dyn_ref::<web_sys::KeyboardEvent>() else {
+            return;
+        };
+        if handle.tool.get_untracked().is_none() {
+            return;
+        }
+        match event.key().as_str() {
+            "Escape" => handle.cancel(),
+            "Enter" => handle.finish(),
+            _ => {}
+        }
+    };
+
+    Effect::new(move |_| {
+        let Some(map) = map_context.map() else {
+            return;
+        };
+        listeners.set_value(vec![
+            LeafletEventListener::new(&map, "click", on_click),
+            LeafletEventListener::new(&map, "mousemove", on_mouse_move),
+        ]);
+        key_listener.set_value(Some(DomEventListener::new(
+            &document(),
+            "keydown",
+            on_key_down,
+        )));
+        set_drawing_mode(&map, map_context, handle.tool.get_untracked().is_some());
+    });
+
+    let tool_stop = Effect::watch(
+        move || handle.tool.get(),
+        move |tool, _, _| {
+            sketch.set_value(tool.map(Sketch::new));
+            cursor.set_value(None);
+            update_preview();
+            if let Some(map) = map_context.map_untracked() {
+                set_drawing_mode(&map, map_context, tool.is_some());
+            }
+        },
+        false,
+    );
+
+    let finish_stop = Effect::watch(
+        move || handle.finish_requested.track(),
+        move |_, _, _| {
+            let Some(map) = map_context.map_untracked() else {
+                return;
+            };
+            let shape = sketch.with_value(|sketch| {
+                sketch
+                    .as_ref()
+                    .and_then(|sketch| sketch.finish(|a, b| map_distance(&map, a, b)))
+            });
+            if let Some(shape) = shape {
+                complete(shape);
+            }
+        },
+        false,
+    );
+
+    on_cleanup(move || {
+        tool_stop.stop();
+        finish_stop.stop();
+        listeners.try_update_value(Vec::clear);
+        key_listener.try_set_value(None);
+        if let Some(layer) = preview.try_update_value(|preview| preview.take()).flatten() {
+            layer.layer().remove();
+        }
+        if let Some(map) = map_context.map_untracked() {
+            set_drawing_mode(&map, map_context, false);
+        }
+    });
+
+    (!tools.is_empty()).then(|| {
+        view! {
+            <Control leaflet_bar=true position=position>
+                {tools
+                    .iter()
+                    .map(|tool| {
+                        let tool = *tool;
+                        view! {
+                            <a
+                                href="#"
+                                role="button"
+                                title=tool.name()
+                                class=move || {
+                                    if handle.tool.get() == Some(tool) {
+                                        "leaflet-draw-tool leaflet-draw-tool-active"
+                                    } else {
+                                        "leaflet-draw-tool"
+                                    }
+                                }
+                                on:click=move |event| {
+                                    // Not a click on the map
+                                    event.prevent_default();
+                                    event.stop_propagation();
+                                    if handle.tool.get_untracked() == Some(tool) {
+                                        handle.cancel();
+                                    } else {
+                                        handle.start(tool);
+                                    }
+                                }
+                            >
+                                {tool.name()}
+                            </a>
+                        }
+                    })
+                    .collect_view()}
+            </Control>
+        }
+    })
+}
+
+fn layer_point(map: &leaflet::Map, position: &Position) -> (f64, f64) {
+    let point = map.lat_lng_to_layer_point(&position.as_lat_lng());
+    (point.x(), point.y())
+}
+
+/// The distance in meters with the CRS of the map, or in map units for `Crs::Simple`.
+pub(crate) fn map_distance(map: &leaflet::Map, a: &Position, b: &Position) -> f64 {
+    call_method(
+        map,
+        "distance",
+        &[a.as_lat_lng().into(), b.as_lat_lng().into()],
+    )
+    .and_then(|distance| distance.as_f64())
+    .unwrap_or_else(|| a.distance_haversine(b))
+}
+
+/// Shows a crosshair cursor while drawing, and keeps double clicks on vertices from zooming.
+fn set_drawing_mode(map: &leaflet::Map, map_context: LeafletMapContext, drawing: bool) {
+    let class_list = map.get_container().class_list();
+    if drawing {
+        let _ = class_list.add_1("leaflet-crosshair");
+    } else {
+        let _ = class_list.remove_1("leaflet-crosshair");
+    }
+    map_context.suspend_double_click_zoom(drawing);
+}
+
+/// The layer showing the shape being drawn, updated in place while the cursor moves.
+enum PreviewLayer {
+    Polyline(leaflet::Polyline),
+    Polygon(leaflet::Polygon),
+    Rectangle(leaflet::Rectangle),
+    Circle(leaflet::Circle),
+}
+
+impl PreviewLayer {
+    /// Creates the layer of a shape, which doesn't catch the clicks on the map.
+    fn new(shape: &DrawnShape, path_options: Option<&PathOptions>) -> Option<Self> {
+        let polyline_options = || {
+            let options = PolylineOptions::new();
+            if let Some(path_options) = path_options {
+                Object::assign(options.unchecked_ref(), path_options.unchecked_ref());
+            }
+            options.set_interactive(false);
+            options
+        };
+        let layer = match shape {
+            DrawnShape::Marker(_) => return None,
+            DrawnShape::Polyline(positions) => {
+                PreviewLayer::Polyline(leaflet::Polyline::new_with_options(
+                    &to_lat_lng_array(positions),
+                    &polyline_options(),
+                ))
+            }
+            DrawnShape::Polygon(positions) => {
+                PreviewLayer::Polygon(leaflet::Polygon::new_with_options(
+                    &to_lat_lng_array(positions),
+                    &polyline_options(),
+                ))
+            }
+            DrawnShape::Rectangle(bounds) => {
+                PreviewLayer::Rectangle(leaflet::Rectangle::new_with_options(
+                    &bounds.as_lat_lng_bounds(),
+                    &polyline_options(),
+                ))
+            }
+            DrawnShape::Circle { center, radius } => {
+                let options = CircleOptions::new();
+                if let Some(path_options) = path_options {
+                    Object::assign(options.unchecked_ref(), path_options.unchecked_ref());
+                }
+                options.set_interactive(false);
+                options.set_radius(*radius);
+                PreviewLayer::Circle(leaflet::Circle::new_with_options(
+                    &center.as_lat_lng(),
+                    &options,
+                ))
+            }
+        };
+        Some(layer)
+    }
+
+    /// Shows `shape` with the layer, returns false if the layer is of another kind of shape.
+    fn update(&self, shape: &DrawnShape) -> bool {
+        match (self, shape) {
+            (PreviewLayer::Polyline(layer), DrawnShape::Polyline(positions)) => {
+                layer.set_lat_lngs(&to_lat_lng_array(positions));
+            }
+            (PreviewLayer::Polygon(layer), DrawnShape::Polygon(positions)) => {
+                layer.set_lat_lngs(&to_lat_lng_array(positions));
+            }
+            (PreviewLayer::Rectangle(layer), DrawnShape::Rectangle(bounds)) => {
+                layer.set_bounds(&bounds.as_lat_lng_bounds());
+            }
+            (PreviewLayer::Circle(layer), DrawnShape::Circle { center, radius }) => {
+                layer.set_lat_lng(&center.as_lat_lng());
+                layer.set_radius(*radius);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn layer(&self) -> leaflet::Layer {
+        match self {
+            PreviewLayer::Polyline(layer) => layer.clone().into(),
+            PreviewLayer::Polygon(layer) => layer.clone().into(),
+            PreviewLayer::Rectangle(layer) => layer.clone().into(),
+            PreviewLayer::Circle(layer) => layer.clone().into(),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::leaflet_event;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Event fired when a layer of a `LayersControl` is selected or unselected.
+#[derive(Debug, Clone)]
+pub struct LayersControlEvent {
+    /// Name of the `BaseLayer` or `Overlay`.
+    pub name: String,
+    /// The group holding the layers of the `BaseLayer` or `Overlay`.
+    pub layer: leaflet::Layer,
+}
+
+#[derive(Clone, Default)]
+pub struct LayersControlEvents {
+    inner: Rc<RefCell<InnerLayersControlEvents>>,
+}
+
+#[derive(Default)]
+struct InnerLayersControlEvents {
+    on_base_layer_change: Option<Box<dyn Fn(LayersControlEvent)>>,
+    on_overlay_add: Option<Box<dyn Fn(LayersControlEvent)>>,
+    on_overlay_remove: Option<Box<dyn Fn(LayersControlEvent)>>,
+}
+
+impl LayersControlEvents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn base_layer_change(&self, event: LayersControlEvent) {
+        if let Some(on_base_layer_change) = &self.inner.borrow().on_base_layer_change {
+            on_base_layer_change(event);
+        }
+    }
+
+    pub(crate) fn overlay_add(&self, event: LayersControlEvent) {
+        if let Some(on_overlay_add) = &self.inner.borrow().on_overlay_add {
+            on_overlay_add(event);
+        }
+    }
+
+    pub(crate) fn overlay_remove(&self, event: LayersControlEvent) {
+        if let Some(on_overlay_remove) = &self.inner.borrow().on_overlay_remove {
+            on_overlay_remove(event);
+        }
+    }
+}
+
+leaflet_event!(
+    LayersControlEvents,
+    on_base_layer_change,
+    LayersControlEvent
+);
+leaflet_event!(LayersControlEvents, on_overlay_add, LayersControlEvent);
+leaflet_event!(LayersControlEvents, on_overlay_remove, LayersControlEvent);
@@ -1,18 +1,22 @@
 mod drag_events;
 mod layer_events;
+mod layers_control_events;
 mod map_events;
 mod mouse_events;
 mod move_events;
 mod popup_events;
 mod tooltip_events;
+mod video_events;
 
 pub use drag_events::DragEvents;
 pub use layer_events::LayerEvents;
+pub use layers_control_events::{LayersControlEvent, LayersControlEvents};
 pub use map_events::MapEvents;
 pub use mouse_events::MouseEvents;
 pub use move_events::MoveEvents;
 pub use popup_events::PopupEvents;
 pub use tooltip_events::TooltipEvents;
+pub use video_events::{VideoError, VideoEvents};
 
 #[macro_export]
 macro_rules! leaflet_event {
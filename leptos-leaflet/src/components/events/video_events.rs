@@ -0,0 +1,52 @@
+use crate::leaflet_event;
+use std::cell::RefCell;
+use std::rc::Rc;
+use web_sys::Event;
+
+/// Error of the video element of a `VideoOverlay`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoError {
+    /// The `MediaError` code, e.g. 4 when the source is not supported.
+    pub code: u16,
+    pub message: String,
+}
+
+#[derive(Clone, Default)]
+pub struct VideoEvents {
+    inner: Rc<RefCell<InnerVideoEvents>>,
+}
+
+#[derive(Default)]
+struct InnerVideoEvents {
+    on_ended: Option<Box<dyn Fn(Event)>>,
+    on_time_update: Option<Box<dyn Fn(f64)>>,
+    on_error: Option<Box<dyn Fn(VideoError)>>,
+}
+
+impl VideoEvents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn ended(&self, event: Event) {
+        if let Some(on_ended) = &self.inner.borrow().on_ended {
+            on_ended(event);
+        }
+    }
+
+    pub(crate) fn time_update(&self, current_time: f64) {
+        if let Some(on_time_update) = &self.inner.borrow().on_time_update {
+            on_time_update(current_time);
+        }
+    }
+
+    pub(crate) fn error(&self, error: VideoError) {
+        if let Some(on_error) = &self.inner.borrow().on_error {
+            on_error(error);
+        }
+    }
+}
+
+leaflet_event!(VideoEvents, on_ended, Event);
+leaflet_event!(VideoEvents, on_time_update, f64);
+leaflet_event!(VideoEvents, on_error, VideoError);
@@ -0,0 +1,342 @@
+use std::collections::{HashMap, HashSet};
+
+use geojson::{feature::Id, Feature, GeoJson as GeoJsonData};
+use js_sys::Object;
+use leaflet::PathOptions;
+use leptos::logging::error;
+use leptos::prelude::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use super::{
+    extend_context_with_overlay, use_layer_parent, use_pane_context, LayerEvents, MouseEvents,
+    PaneRendererScope, PopupEvents, Position, TooltipEvents,
+};
+use crate::core::{
+    call_method, get_function, leaflet_namespace, set_option, JsRwSignal, JsStoredValue,
+};
+
+/// Renders GeoJSON data on the map.
+///
+/// Every feature becomes its own layer. When `data` changes, features are matched by their `id`
+/// (or by their index when they have none), and only the added, removed or modified ones are
+/// recreated. A `serde_json::Value` can be converted with `GeoJson::try_from(value)`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use leptos::prelude::*;
+/// use leptos_leaflet::geojson::{Feature, GeoJson as GeoJsonData};
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn Countries(data: Signal<GeoJsonData>) -> impl IntoView {
+///     view! {
+///         <GeoJson
+///             data=data
+///             style=|_: Feature| {
+///                 let options = PathOptions::new();
+///                 options.set_color("purple".to_string());
+///                 options
+///             }
+///             on_each_feature=|feature: Feature| {
+///                 let name = feature
+///                     .property("name")
+///                     .and_then(|name| name.as_str())
+///                     .unwrap_or_default()
+///                     .to_string();
+///                 view! { <Tooltip sticky=true>{name}</Tooltip> }.into_any()
+///             }
+///         />
+///     }
+/// }
+/// ```
+#[component(transparent)]
+pub fn GeoJson(
+    /// The GeoJSON to render, a `FeatureCollection`, a single `Feature` or a `Geometry`.
+    #[prop(into)]
+    data: Signal<GeoJsonData>,
+    /// Returns the style of the vector layers of a feature, re-applied when its signals change.
+    #[prop(into, optional)]
+    style: Option<Callback<Feature, PathOptions>>,
+    /// Creates the layer of a point, defaults to a `leaflet::Marker`.
+    #[prop(into, optional)]
+    point_to_layer: Option<Callback<(Feature, Position), leaflet::Layer>>,
+    /// Returns `false` for the features that must not be rendered.
+    #[prop(into, optional)]
+    filter: Option<Callback<Feature, bool>>,
+    /// Renders the children of a feature, like a `Popup` or a `Tooltip` bound to its layer.
+    #[prop(into, optional)]
+    on_each_feature: Option<Callback<Feature, AnyView>>,
+    #[prop(into, optional)] mouse_events: MouseEvents,
+    #[prop(into, optional)] layer_events: LayerEvents,
+    #[prop(into, optional)] popup_events: PopupEvents,
+    #[prop(into, optional)] tooltip_events: TooltipEvents,
+) -> impl IntoView {
+    let layer_parent = use_layer_parent();
+    let group = JsRwSignal::new_local(None::<leaflet::FeatureGroup>);
+
+    Effect::new(move |_| {
+        if layer_parent.is_ready() {
+            // Events of the features bubble up to the group
+            let feature_group = leaflet::FeatureGroup::new();
+            mouse_events.setup(&feature_group);
+            layer_events.setup(&feature_group);
+            popup_events.setup(&feature_group);
+            tooltip_events.setup(&feature_group);
+
+            layer_parent.add_layer(&feature_group);
+            group.set(Some(feature_group));
+        }
+    });
+
+    let features = Memo::new(move |previous: Option<&Vec<KeyedFeature>>| {
+        let features = data.with(collect_features);
+        let features = match filter {
+            Some(filter) => features
+                .into_iter()
+                .filter(|feature| filter.run(feature.clone()))
+                .collect(),
+            None => features,
+        };
+        key_features(features, previous.map(Vec::as_slice).unwrap_or_default())
+    });
+
+    on_cleanup(move || {
+        if let Some(group) = group.try_get_untracked().flatten() {
+            layer_parent.remove_layer(&group);
+        }
+    });
+
+    view! {
+        <For
+            each=move || features.get()
+            key=|feature| (feature.key.clone(), feature.revision)
+            children=move |feature| {
+                view! {
+                    <GeoJsonFeature
+                        feature=feature.feature
+                        group=group
+                        style=style
+                        point_to_layer=point_to_layer
+                        on_each_feature=on_each_feature
+                    />
+                }
+            }
+        />
+    }
+}
+
+/// The layer of a single feature, added to the group of its `GeoJson`.
+#[component(transparent)]
+fn GeoJsonFeature(
+    feature: Feature,
+    group: JsRwSignal<Option<leaflet::FeatureGroup>>,
+    style: Option<Callback<Feature, PathOptions>>,
+    point_to_layer: Option<Callback<(Feature, Position), leaflet::Layer>>,
+    on_each_feature: Option<Callback<Feature, AnyView>>,
+) -> impl IntoView {
+    let overlay_context = extend_context_with_overlay();
+    let overlay = JsStoredValue::new_local(None::<leaflet::Layer>);
+
+    let layer_feature = feature.clone();
+    Effect::new(move |_| {
+        let Some(group) = group.get() else {
+            return;
+        };
+        let options = Object::new();
+        if let Some(style) = style {
+            let path_options = untrack(|| style.run(layer_feature.clone()));
+            set_option(&options, "style", &path_options);
+        }
+        if let Some(pane_context) = use_pane_context() {
+            set_option(&options, "pane", &JsValue::from_str(pane_context.name()));
+            let renderer = match pane_context.renderer_scope() {
+                PaneRendererScope::PaneSpecificSvg => {
+                    pane_context.svg_renderer().map(JsValue::from)
+                }
+                PaneRendererScope::PaneSpecificCanvas => {
+                    pane_context.canvas_renderer().map(JsValue::from)
+                }
+                PaneRendererScope::Global => None,
+            };
+            if let Some(renderer) = renderer {
+                set_option(&options, "renderer", &renderer);
+            }
+        }
+        // Only needed while Leaflet builds the layers, dropped at the end of the effect
+        let point_to_layer = point_to_layer.map(|point_to_layer| {
+            let feature = layer_feature.clone();
+            Closure::<dyn Fn(JsValue, leaflet::LatLng) -> JsValue>::new(
+                move |_: JsValue, lat_lng: leaflet::LatLng| {
+                    point_to_layer.run((feature.clone(), lat_lng.into())).into()
+                },
+            )
+        });
+        if let Some(point_to_layer) = &point_to_layer {
+            set_option(&options, "pointToLayer", point_to_layer.as_ref());
+        }
+
+        let Some(layer) = create_geo_json_layer(&layer_feature, &options) else {
+            return;
+        };
+        group.add_layer(&layer);
+        overlay_context.set_container(&layer);
+        overlay.set_value(Some(layer));
+    });
+
+    let style_feature = feature.clone();
+    let style_stop = Effect::watch(
+        move || style.map(|style| style.run(style_feature.clone())),
+        move |path_options, _, _| {
+            if let (Some(path_options), Some(layer)) = (path_options, overlay.get_value()) {
+                set_style(&layer, path_options);
+            }
+        },
+        false,
+    );
+
+    on_cleanup(move || {
+        style_stop.stop();
+        if let (Some(group), Some(layer)) = (
+            group.try_get_untracked().flatten(),
+            overlay.try_get_value().flatten(),
+        ) {
+            group.remove_layer(&layer);
+        }
+    });
+
+    on_each_feature.map(|on_each_feature| on_each_feature.run(feature))
+}
+
+/// A feature with the key used to match it between updates of the data.
+#[derive(Debug, Clone, PartialEq)]
+struct KeyedFeature {
+    key: String,
+    /// Increased every time the feature changes, so it gets recreated.
+    revision: u64,
+    feature: Feature,
+}
+
+fn collect_features(data: &GeoJsonData) -> Vec<Feature> {
+    match data {
+        GeoJsonData::FeatureCollection(collection) => collection.features.clone(),
+        GeoJsonData::Feature(feature) => vec![feature.clone()],
+        GeoJsonData::Geometry(geometry) => vec![Feature::from(geometry.clone())],
+    }
+}
+
+/// Keys the features by id, keeping the revision of the unchanged ones.
+fn key_features(features: Vec<Feature>, previous: &[KeyedFeature]) -> Vec<KeyedFeature> {
+    let previous = previous
+        .iter()
+        .map(|keyed| (keyed.key.as_str(), keyed))
+        .collect::<HashMap<_, _>>();
+    let mut used_keys = HashSet::new();
+    features
+        .into_iter()
+        .enumerate()
+        .map(|(index, feature)| {
+            let mut key = match &feature.id {
+                // Prefixed by the kind of id, the string "1" and the number 1 are different ids
+                Some(Id::String(id)) => format!("s:{id}"),
+                Some(Id::Number(id)) => format!("n:{id}"),
+                None => format!("index:{index}"),
+            };
+            // Duplicated ids fall back to the index, keys must be unique
+            if used_keys.contains(&key) {
+                key = format!("{key}@{index}");
+            }
+            used_keys.insert(key.clone());
+            let revision = match previous.get(key.as_str()) {
+                Some(keyed) if keyed.feature == feature => keyed.revision,
+                Some(keyed) => keyed.revision + 1,
+                None => 0,
+            };
+            KeyedFeature {
+                key,
+                revision,
+                feature,
+            }
+        })
+        .collect()
+}
+
+/// Creates the layer of a feature with `L.geoJSON`.
+fn create_geo_json_layer(feature: &Feature, options: &Object) -> Option<leaflet::Layer> {
+    let leaflet = leaflet_namespace()?;
+    let geo_json = get_function(&leaflet, "geoJSON")?;
+    let data = js_sys::JSON::parse(&feature.to_string()).ok()?;
+    match geo_json.call2(&leaflet, &data, options) {
+        Ok(layer) => Some(layer.unchecked_into()),
+        Err(err) => {
+            error!("Failed to create GeoJSON layer: {:?}", err);
+            None
+        }
+    }
+}
+
+fn set_style(layer: &leaflet::Layer, path_options: &PathOptions) {
+    call_method(layer, "setStyle", &[path_options.clone().into()]);
+}
+
+#[cfg(test)]
+mod tests {
+    use geojson::{Geometry, Value};
+
+    use super::*;
+
+    fn point(id: Option<Id>, x: f64, y: f64) -> Feature {
+        Feature {
+            id,
+            ..Feature::from(Geometry::new(Value::Point(vec![x, y])))
+        }
+    }
+
+    #[test]
+    fn test_key_features_by_id() {
+        let id = |id: &str| Some(Id::String(id.to_string()));
+        let first = key_features(
+            vec![point(id("a"), 0.0, 0.0), point(id("b"), 1.0, 1.0)],
+            &[],
+        );
+        assert_eq!(first[0].key, "s:a");
+        assert_eq!(first[1].key, "s:b");
+
+        // Reordered and modified features keep their keys, only modified ones get a new revision
+        let second = key_features(
+            vec![point(id("b"), 2.0, 2.0), point(id("a"), 0.0, 0.0)],
+            &first,
+        );
+        assert_eq!((second[0].key.as_str(), second[0].revision), ("s:b", 1));
+        assert_eq!((second[1].key.as_str(), second[1].revision), ("s:a", 0));
+    }
+
+    #[test]
+    fn test_key_features_without_id() {
+        let keyed = key_features(vec![point(None, 0.0, 0.0), point(None, 1.0, 1.0)], &[]);
+        assert_eq!(keyed[0].key, "index:0");
+        assert_eq!(keyed[1].key, "index:1");
+    }
+
+    #[test]
+    fn test_key_features_duplicated_ids() {
+        let id = Some(Id::Number(1.into()));
+        let keyed = key_features(vec![point(id.clone(), 0.0, 0.0), point(id, 1.0, 1.0)], &[]);
+        assert_eq!(keyed[0].key, "n:1");
+        assert_eq!(keyed[1].key, "n:1@1");
+    }
+
+    #[test]
+    fn test_key_features_string_and_number_ids() {
+        let keyed = key_features(
+            vec![
+                point(Some(Id::String("1".to_string())), 0.0, 0.0),
+                point(Some(Id::Number(1.into())), 1.0, 1.0),
+            ],
+            &[],
+        );
+        assert_eq!(keyed[0].key, "s:1");
+        assert_eq!(keyed[1].key, "n:1");
+    }
+}
@@ -1,13 +1,16 @@
-use super::{use_pane_context, Bounds, LeafletMapContext};
-use crate::core::IntoThreadSafeJsValue;
+use super::{use_layer_parent, use_pane_context, Bounds};
+use crate::core::JsStoredValue;
 use leptos::logging::log;
 use leptos::prelude::*;
 
 /// An image overlay component.
+///
+/// Changes of `url`, `bounds`, `opacity` and `z_index` update the overlay in place, for example
+/// to animate frames without re-adding the layer.
 #[component(transparent)]
 pub fn ImageOverlay(
-    #[prop(into)] url: String,
-    #[prop(into)] bounds: Bounds,
+    #[prop(into)] url: Signal<String>,
+    #[prop(into)] bounds: Signal<Bounds>,
     #[prop(into, optional)] opacity: Option<Signal<f64>>,
     #[prop(into, optional)] alt: Option<Signal<String>>,
     #[prop(into, optional)] interactive: Option<Signal<bool>>,
@@ -20,9 +23,12 @@ pub fn ImageOverlay(
     #[prop(into, optional)] pane: Option<Signal<String>>,
     #[prop(into, optional)] attribution: Option<Signal<String>>,
 ) -> impl IntoView {
-    let map_context = use_context::<LeafletMapContext>().expect("map context not found");
+    let layer_parent = use_layer_parent();
+    let overlay = JsStoredValue::new_local(None::<leaflet::ImageOverlay>);
+
     Effect::new(move |_| {
-        if let Some(map) = map_context.map() {
+        if layer_parent.is_ready() {
+            let url = url.get_untracked();
             log!("Adding image layer: {}", url);
             let options = leaflet::ImageOverlayOptions::new();
             if let Some(opacity) = opacity {
@@ -68,14 +74,61 @@ pub fn ImageOverlay(
 
             let map_layer = leaflet::ImageOverlay::new_with_options(
                 &url,
-                &bounds.as_lat_lng_bounds(),
+                &bounds.get_untracked().as_lat_lng_bounds(),
                 &options,
-            )
-            .into_thread_safe_js_value();
-            map_layer.add_to(&map);
-            on_cleanup(move || {
-                map_layer.remove();
-            });
+            );
+            layer_parent.add_layer(&map_layer);
+            overlay.set_value(Some(map_layer));
+        }
+    });
+
+    let url_stop = Effect::watch(
+        move || url.get(),
+        move |url, _, _| {
+            if let Some(overlay) = overlay.get_value() {
+                overlay.set_url(url);
+            }
+        },
+        false,
+    );
+
+    let bounds_stop = Effect::watch(
+        move || bounds.get(),
+        move |bounds, _, _| {
+            if let Some(overlay) = overlay.get_value() {
+                overlay.set_bounds(&bounds.as_lat_lng_bounds());
+            }
+        },
+        false,
+    );
+
+    let opacity_stop = Effect::watch(
+        move || opacity.map(|opacity| opacity.get()),
+        move |opacity, _, _| {
+            if let (Some(opacity), Some(overlay)) = (opacity, overlay.get_value()) {
+                overlay.set_opacity(*opacity);
+            }
+        },
+        false,
+    );
+
+    let z_index_stop = Effect::watch(
+        move || z_index.map(|z_index| z_index.get()),
+        move |z_index, _, _| {
+            if let (Some(z_index), Some(overlay)) = (z_index, overlay.get_value()) {
+                overlay.set_z_index(*z_index);
+            }
+        },
+        false,
+    );
+
+    on_cleanup(move || {
+        url_stop.stop();
+        bounds_stop.stop();
+        opacity_stop.stop();
+        z_index_stop.stop();
+        if let Some(overlay) = overlay.try_get_value().flatten() {
+            layer_parent.remove_layer(&overlay);
         }
     });
 }
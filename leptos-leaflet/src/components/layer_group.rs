@@ -0,0 +1,267 @@
+use leaflet::PathOptions;
+use leptos::prelude::*;
+
+use super::{
+    extend_context_with_overlay, provide_layer_group_context, use_layer_parent, Bounds,
+    LayerEvents, LayerParent, MouseEvents, PopupEvents, StringEmptyOption, TooltipEvents,
+};
+use crate::core::{JsStoredValue, LeafletEventListener};
+
+/// A group of layers, handled as one.
+///
+/// The layers of the children are added to the group instead of the map, so hiding the group
+/// hides all of them.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use leptos::prelude::*;
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn Stops(visible: Signal<bool>) -> impl IntoView {
+///     view! {
+///         <LayerGroup visible=visible>
+///             <Marker position=position!(51.505, -0.09) />
+///             <Marker position=position!(51.51, -0.1) />
+///         </LayerGroup>
+///     }
+/// }
+/// ```
+#[component(transparent)]
+pub fn LayerGroup(
+    /// Whether the group is shown on the map.
+    #[prop(into, optional, default = true.into())]
+    visible: Signal<bool>,
+    #[prop(into, optional)] layer_events: LayerEvents,
+    #[prop(optional)] children: Option<Children>,
+) -> impl IntoView {
+    let layer_parent = use_layer_parent();
+    let group_context = provide_layer_group_context();
+
+    Effect::new(move |_| {
+        if layer_parent.is_ready() {
+            let group = leaflet::LayerGroup::new();
+            layer_events.setup(&group);
+            if visible.get_untracked() {
+                layer_parent.add_layer(&group);
+            }
+            group_context.set_group(&group);
+        }
+    });
+
+    let visible_stop = Effect::watch(
+        move || visible.get(),
+        move |&visible, _, _| {
+            if let Some(group) = group_context.group_untracked() {
+                set_visible(&layer_parent, &group, visible);
+            }
+        },
+        false,
+    );
+
+    on_cleanup(move || {
+        visible_stop.stop();
+        if let Some(group) = group_context.group_untracked() {
+            layer_parent.remove_layer(&group);
+        }
+    });
+
+    children.map(|child| child())
+}
+
+/// A group of layers sharing events, style and bounds.
+///
+/// Mouse, popup and tooltip events of the members bubble up to the group. A `Popup` or `Tooltip`
+/// passed as child is bound to all the members.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use leptos::prelude::*;
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn Area() -> impl IntoView {
+///     let bounds = RwSignal::new(None::<Bounds>);
+///     let map = use_map();
+///     Effect::new(move |_| {
+///         if let Some(bounds) = bounds.get() {
+///             let _ = map.fit_bounds(bounds, None);
+///         }
+///     });
+///     view! {
+///         <FeatureGroup color="red" bounds_signal=bounds>
+///             <Circle center=position!(51.505, -0.09) radius=200.0 />
+///             <Marker position=position!(51.51, -0.1) />
+///         </FeatureGroup>
+///     }
+/// }
+/// ```
+#[component(transparent)]
+pub fn FeatureGroup(
+    /// Whether the group is shown on the map.
+    #[prop(into, optional, default = true.into())]
+    visible: Signal<bool>,
+    /// Stroke color applied to all the members.
+    #[prop(into, optional)]
+    color: Signal<String>,
+    #[prop(into, optional)] weight: Signal<Option<f64>>,
+    #[prop(into, optional)] opacity: Signal<Option<f64>>,
+    #[prop(into, optional)] dash_array: Signal<String>,
+    #[prop(into, optional)] fill_color: Signal<String>,
+    #[prop(into, optional)] fill_opacity: Signal<Option<f64>>,
+    /// Updated with the bounds of the members, `None` while the group is empty.
+    #[prop(optional)]
+    bounds_signal: Option<RwSignal<Option<Bounds>>>,
+    #[prop(into, optional)] mouse_events: MouseEvents,
+    #[prop(into, optional)] layer_events: LayerEvents,
+    #[prop(into, optional)] popup_events: PopupEvents,
+    #[prop(into, optional)] tooltip_events: TooltipEvents,
+    #[prop(optional)] children: Option<Children>,
+) -> impl IntoView {
+    let layer_parent = use_layer_parent();
+    let group_context = provide_layer_group_context();
+    let overlay_context = extend_context_with_overlay();
+    let overlay = JsStoredValue::new_local(None::<leaflet::FeatureGroup>);
+    let listeners = JsStoredValue::new_local(Vec::<LeafletEventListener>::new());
+    let style = JsStoredValue::new_local(None::<PathOptions>);
+
+    Effect::new(move |_| {
+        if layer_parent.is_ready() {
+            let group = leaflet::FeatureGroup::new();
+            mouse_events.setup(&group);
+            layer_events.setup(&group);
+            popup_events.setup(&group);
+            tooltip_events.setup(&group);
+
+            // Members added later get the style of the group too, and change its bounds
+            let style_group = group.clone();
+            let bounds_group = group.clone();
+            listeners.set_value(vec![
+                LeafletEventListener::new(&group, "layeradd", move |_: leaflet::Event| {
+                    if let Some(style) = style.get_value() {
+                        style_group.set_style(&style);
+                    }
+                }),
+                LeafletEventListener::new(
+                    &group,
+                    "layeradd layerremove move",
+                    move |_: leaflet::Event| {
+                        if let Some(bounds_signal) = bounds_signal {
+                            update_bounds(&bounds_group, bounds_signal);
+                        }
+                    },
+                ),
+            ]);
+
+            if visible.get_untracked() {
+                layer_parent.add_layer(&group);
+            }
+            group_context.set_group(&group);
+            overlay_context.set_container(&group);
+            overlay.set_value(Some(group));
+        }
+    });
+
+    let visible_stop = Effect::watch(
+        move || visible.get(),
+        move |&visible, _, _| {
+            if let Some(group) = overlay.get_value() {
+                set_visible(&layer_parent, &group, visible);
+            }
+        },
+        false,
+    );
+
+    let style_stop = Effect::watch(
+        move || {
+            (
+                color.get(),
+                weight.get(),
+                opacity.get(),
+                dash_array.get(),
+                fill_color.get(),
+                fill_opacity.get(),
+            )
+        },
+        move |(color, weight, opacity, dash_array, fill_color, fill_opacity), _, _| {
+            let options = PathOptions::new();
+            let mut has_style = false;
+            if let Some(color) = color.to_option() {
+                options.set_color(color.to_string());
+                has_style = true;
+            }
+            if let Some(weight) = weight {
+                options.set_weight(*weight);
+                has_style = true;
+            }
+            if let Some(opacity) = opacity {
+                options.set_opacity(*opacity);
+                has_style = true;
+            }
+            if let Some(dash_array) = dash_array.to_option() {
+                options.set_dash_array(dash_array.to_string());
+                has_style = true;
+            }
+            if let Some(fill_color) = fill_color.to_option() {
+                options.set_fill_color(fill_color.to_string());
+                has_style = true;
+            }
+            if let Some(fill_opacity) = fill_opacity {
+                options.set_fill_opacity(*fill_opacity);
+                has_style = true;
+            }
+            if !has_style {
+                return;
+            }
+            if let Some(group) = overlay.get_value() {
+                group.set_style(&options);
+            }
+            style.set_value(Some(options));
+        },
+        true,
+    );
+
+    on_cleanup(move || {
+        visible_stop.stop();
+        style_stop.stop();
+        listeners.try_update_value(Vec::clear);
+        if let Some(group) = overlay.try_get_value().flatten() {
+            layer_parent.remove_layer(&group);
+        }
+    });
+
+    children.map(|child| child())
+}
+
+/// Adds the group to its parent, or removes it.
+fn set_visible<G: Into<leaflet::Layer> + Clone>(
+    layer_parent: &LayerParent,
+    group: &G,
+    visible: bool,
+) {
+    if visible {
+        layer_parent.add_layer(group);
+    } else {
+        layer_parent.remove_layer(group);
+    }
+}
+
+fn update_bounds(group: &leaflet::FeatureGroup, bounds_signal: RwSignal<Option<Bounds>>) {
+    // The bounds of an empty group have no corners
+    let lat_lng_bounds = group.get_bounds();
+    let bounds = lat_lng_bounds
+        .is_valid()
+        .then(|| Bounds::from(lat_lng_bounds));
+    let changed =
+        bounds_signal
+            .try_get_untracked()
+            .is_some_and(|current| match (current, bounds) {
+                (Some(current), Some(bounds)) => !current.approx_eq(&bounds),
+                (current, bounds) => current.is_some() != bounds.is_some(),
+            });
+    if changed {
+        bounds_signal.try_set(bounds);
+    }
+}
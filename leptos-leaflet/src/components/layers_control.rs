@@ -0,0 +1,380 @@
+use js_sys::{Object, Reflect};
+use leptos::logging::error;
+use leptos::prelude::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use super::{
+    provide_layer_group_context, use_leaflet_context, LayersControlEvent, LayersControlEvents,
+};
+use crate::core::{
+    call_method, get_function, leaflet_namespace, set_option, JsRwSignal, JsStoredValue,
+    LeafletEventListener,
+};
+
+/// A control to switch between base layers and toggle overlays.
+///
+/// The layers are declared with [`BaseLayer`] and [`Overlay`] children, which can wrap any layer
+/// component, including groups.
+/// Only one base layer is shown at a time: the one named by `active_base_layer`, or else the first
+/// checked one.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use leptos::prelude::*;
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn Layers() -> impl IntoView {
+///     let base_layer = RwSignal::new(Some("Streets".to_string()));
+///     view! {
+///         <LayersControl active_base_layer=base_layer>
+///             <BaseLayer name="Streets" checked=true>
+///                 <TileLayer url="https://tile.openstreetmap.org/{z}/{x}/{y}.png" />
+///             </BaseLayer>
+///             <BaseLayer name="Topography">
+///                 <TileLayer url="https://tile.opentopomap.org/{z}/{x}/{y}.png" />
+///             </BaseLayer>
+///             <Overlay name="Stops">
+///                 <Marker position=position!(51.505, -0.09) />
+///             </Overlay>
+///         </LayersControl>
+///     }
+/// }
+/// ```
+#[component]
+pub fn LayersControl(
+    /// Position of the control.
+    #[prop(optional, into, default = Signal::derive(|| "topright".to_string()))]
+    position: Signal<String>,
+    /// Wether the control is collapsed into an icon until hovered.
+    #[prop(optional, default = true)]
+    collapsed: bool,
+    /// Wether the base layers are hidden when there is only one.
+    #[prop(optional)]
+    hide_single_base: bool,
+    /// Wether the layers are sorted by name.
+    #[prop(optional)]
+    sort_layers: bool,
+    /// Two-way bound name of the active base layer.
+    #[prop(optional)]
+    active_base_layer: Option<RwSignal<Option<String>>>,
+    /// Two-way bound names of the enabled overlays.
+    #[prop(optional)]
+    active_overlays: Option<RwSignal<Vec<String>>>,
+    #[prop(into, optional)] events: LayersControlEvents,
+    children: Children,
+) -> impl IntoView {
+    let map_context = use_leaflet_context().expect("map context not found");
+    let context = LayersControlContext {
+        control: JsRwSignal::new_local(None),
+        layers: JsStoredValue::new_local(Vec::new()),
+        active_base_layer,
+        active_overlays,
+    };
+    provide_context(context);
+    let listeners = JsStoredValue::new_local(Vec::<LeafletEventListener>::new());
+
+    Effect::new(move |_| {
+        let Some(map) = map_context.map() else {
+            return;
+        };
+        let options = Object::new();
+        set_option(&options, "position", &position.get_untracked().into());
+        set_option(&options, "collapsed", &collapsed.into());
+        set_option(&options, "hideSingleBase", &hide_single_base.into());
+        set_option(&options, "sortLayers", &sort_layers.into());
+        let Some(control) = create_layers_control(&options) else {
+            return;
+        };
+
+        let base_layer_events = events.clone();
+        let overlay_add_events = events.clone();
+        let overlay_remove_events = events.clone();
+        listeners.set_value(vec![
+            LeafletEventListener::new(&map, "baselayerchange", move |event: JsValue| {
+                let event = layers_control_event(&event);
+                if let Some(active_base_layer) = active_base_layer {
+                    let current = active_base_layer.try_get_untracked().flatten();
+                    if current.as_ref() != Some(&event.name) {
+                        active_base_layer.try_set(Some(event.name.clone()));
+                    }
+                }
+                base_layer_events.base_layer_change(event);
+            }),
+            LeafletEventListener::new(&map, "overlayadd", move |event: JsValue| {
+                let event = layers_control_event(&event);
+                if let Some(active_overlays) = active_overlays {
+                    if active_overlays
+                        .try_with_untracked(|overlays| !overlays.contains(&event.name))
+                        .unwrap_or_default()
+                    {
+                        active_overlays.try_update(|overlays| overlays.push(event.name.clone()));
+                    }
+                }
+                overlay_add_events.overlay_add(event);
+            }),
+            LeafletEventListener::new(&map, "overlayremove", move |event: JsValue| {
+                let event = layers_control_event(&event);
+                if let Some(active_overlays) = active_overlays {
+                    if active_overlays
+                        .try_with_untracked(|overlays| overlays.contains(&event.name))
+                        .unwrap_or_default()
+                    {
+                        active_overlays
+                            .try_update(|overlays| overlays.retain(|name| name != &event.name));
+                    }
+                }
+                overlay_remove_events.overlay_remove(event);
+            }),
+        ]);
+
+        control.add_to(&map);
+        context.control.set(Some(control));
+    });
+
+    let position_stop = Effect::watch(
+        move || position.get(),
+        move |position, _, _| {
+            if let Some(control) = context.control.get_untracked() {
+                control.set_position(position);
+            }
+        },
+        false,
+    );
+
+    let base_layer_stop = Effect::watch(
+        move || active_base_layer.map(|active_base_layer| active_base_layer.get()),
+        move |active_base_layer, _, _| {
+            if let (Some(Some(name)), Some(map)) = (active_base_layer, map_context.map_untracked())
+            {
+                context.show_base_layer(&map, name);
+            }
+        },
+        false,
+    );
+
+    let overlays_stop = Effect::watch(
+        move || active_overlays.map(|active_overlays| active_overlays.get()),
+        move |active_overlays, _, _| {
+            if let (Some(names), Some(map)) = (active_overlays, map_context.map_untracked()) {
+                context.show_overlays(&map, names);
+            }
+        },
+        false,
+    );
+
+    on_cleanup(move || {
+        position_stop.stop();
+        base_layer_stop.stop();
+        overlays_stop.stop();
+        listeners.try_update_value(Vec::clear);
+        if let Some(control) = context.control.try_get_untracked().flatten() {
+            control.remove();
+        }
+    });
+
+    children()
+}
+
+/// A base layer of a [`LayersControl`], only one of them is shown at a time.
+#[component(transparent)]
+pub fn BaseLayer(
+    /// Name shown in the control.
+    #[prop(into)]
+    name: String,
+    /// Wether this is the initially shown base layer.
+    ///
+    /// Only the first checked base layer is shown, and the `active_base_layer` of the control
+    /// takes precedence when it names a layer.
+    #[prop(optional)]
+    checked: bool,
+    children: Children,
+) -> impl IntoView {
+    control_layer(name, checked, false, children)
+}
+
+/// An overlay of a [`LayersControl`], that can be toggled on and off.
+#[component(transparent)]
+pub fn Overlay(
+    /// Name shown in the control.
+    #[prop(into)]
+    name: String,
+    /// Wether the overlay is initially shown.
+    #[prop(optional)]
+    checked: bool,
+    children: Children,
+) -> impl IntoView {
+    control_layer(name, checked, true, children)
+}
+
+/// Registers a group with the layers of the children in the closest [`LayersControl`].
+fn control_layer(name: String, checked: bool, overlay: bool, children: Children) -> impl IntoView {
+    let map_context = use_leaflet_context().expect("map context not found");
+    let context = use_context::<LayersControlContext>()
+        .expect("BaseLayer and Overlay must be used within a LayersControl");
+    let group_context = provide_layer_group_context();
+    let group = JsStoredValue::new_local(None::<leaflet::LayerGroup>);
+
+    Effect::new(move |_| {
+        let (Some(map), Some(control)) = (map_context.map(), context.control.get()) else {
+            return;
+        };
+        let layer_group = leaflet::LayerGroup::new();
+        let method = if overlay {
+            "addOverlay"
+        } else {
+            "addBaseLayer"
+        };
+        call_method(
+            &control,
+            method,
+            &[layer_group.clone().into(), JsValue::from_str(&name)],
+        );
+        let shown = if overlay {
+            checked || context.is_active(&name, overlay)
+        } else {
+            context.shows_base_layer(&map, &name, checked)
+        };
+        context.layers.update_value(|layers| {
+            layers.push(ControlLayer {
+                name: name.clone(),
+                overlay,
+                group: layer_group.clone(),
+            })
+        });
+        if shown {
+            layer_group.add_to(&map);
+        }
+        group_context.set_group(&layer_group);
+        group.set_value(Some(layer_group));
+    });
+
+    on_cleanup(move || {
+        let Some(layer_group) = group.try_get_value().flatten() else {
+            return;
+        };
+        if let Some(control) = context.control.try_get_untracked().flatten() {
+            call_method(&control, "removeLayer", &[layer_group.clone().into()]);
+        }
+        context.layers.try_update_value(|layers| {
+            layers.retain(|layer| layer.group != layer_group);
+        });
+        layer_group.remove();
+    });
+
+    children()
+}
+
+#[derive(Clone, Copy)]
+struct LayersControlContext {
+    control: JsRwSignal<Option<leaflet::Control>>,
+    layers: JsStoredValue<Vec<ControlLayer>>,
+    active_base_layer: Option<RwSignal<Option<String>>>,
+    active_overlays: Option<RwSignal<Vec<String>>>,
+}
+
+struct ControlLayer {
+    name: String,
+    overlay: bool,
+    group: leaflet::LayerGroup,
+}
+
+impl LayersControlContext {
+    /// Wether the layer is selected by the bound signals.
+    fn is_active(&self, name: &str, overlay: bool) -> bool {
+        if overlay {
+            self.active_overlays.is_some_and(|active_overlays| {
+                active_overlays.with_untracked(|overlays| overlays.iter().any(|o| o == name))
+            })
+        } else {
+            self.active_base_layer.is_some_and(|active_base_layer| {
+                active_base_layer.with_untracked(|active| active.as_deref() == Some(name))
+            })
+        }
+    }
+
+    /// Wether a new base layer is shown: the active one when the bound signal names one, otherwise
+    /// the first checked one.
+    ///
+    /// The bound signal is set to the first checked base layer when it names none.
+    fn shows_base_layer(&self, map: &leaflet::Map, name: &str, checked: bool) -> bool {
+        let active = self
+            .active_base_layer
+            .and_then(|active_base_layer| active_base_layer.get_untracked());
+        if let Some(active) = active {
+            return active == name;
+        }
+        let base_layer_shown = self.layers.with_value(|layers| {
+            layers
+                .iter()
+                .any(|layer| !layer.overlay && map.has_layer(&layer.group))
+        });
+        if !checked || base_layer_shown {
+            return false;
+        }
+        if let Some(active_base_layer) = self.active_base_layer {
+            active_base_layer.set(Some(name.to_string()));
+        }
+        true
+    }
+
+    /// Shows the base layer with the given name, and hides the others.
+    fn show_base_layer(&self, map: &leaflet::Map, name: &str) {
+        self.layers.with_value(|layers| {
+            let base_layers = layers.iter().filter(|layer| !layer.overlay);
+            if !base_layers.clone().any(|layer| layer.name == name) {
+                return;
+            }
+            for layer in base_layers {
+                let shown = map.has_layer(&layer.group);
+                if layer.name == name && !shown {
+                    layer.group.add_to(map);
+                } else if layer.name != name && shown {
+                    layer.group.remove();
+                }
+            }
+        });
+    }
+
+    /// Shows the overlays with the given names, and hides the others.
+    fn show_overlays(&self, map: &leaflet::Map, names: &[String]) {
+        self.layers.with_value(|layers| {
+            for layer in layers.iter().filter(|layer| layer.overlay) {
+                let shown = map.has_layer(&layer.group);
+                let active = names.contains(&layer.name);
+                if active && !shown {
+                    layer.group.add_to(map);
+                } else if !active && shown {
+                    layer.group.remove();
+                }
+            }
+        });
+    }
+}
+
+/// Creates the control with `L.control.layers`.
+fn create_layers_control(options: &Object) -> Option<leaflet::Control> {
+    let control = Reflect::get(&leaflet_namespace()?, &JsValue::from_str("control")).ok()?;
+    let layers = get_function(&control, "layers")?;
+    match layers.call3(&control, &JsValue::UNDEFINED, &JsValue::UNDEFINED, options) {
+        Ok(control) => Some(control.unchecked_into()),
+        Err(err) => {
+            error!("Failed to create layers control: {:?}", err);
+            None
+        }
+    }
+}
+
+fn layers_control_event(event: &JsValue) -> LayersControlEvent {
+    LayersControlEvent {
+        name: Reflect::get(event, &JsValue::from_str("name"))
+            .ok()
+            .and_then(|name| name.as_string())
+            .unwrap_or_default(),
+        layer: Reflect::get(event, &JsValue::from_str("layer"))
+            .unwrap_or(JsValue::UNDEFINED)
+            .unchecked_into(),
+    }
+}
@@ -1,5 +1,5 @@
-use leaflet::Map;
-use leptos::{html::Div, prelude::*};
+use leaflet::{Handler, Map};
+use leptos::{html::Div, logging::error, prelude::*};
 use wasm_bindgen::prelude::*;
 use web_sys::HtmlDivElement;
 
@@ -7,12 +7,19 @@ use leaflet::LocateOptions;
 
 use crate::core::JsWriteSignal;
 
-use super::{provide_leaflet_context, MapEvents, PopupEvents, Position, TooltipEvents};
+use super::{
+    provide_leaflet_context, Bounds, Crs, MapEvents, PopupEvents, Position, TooltipEvents,
+};
 
 /// A container for the Leaflet map.
 /// 
 /// This is the main container for the Leaflet map. It provides a way to add child nodes to the map.
 /// It also provides a signal to access the map instance, allowing to interact with the map from other components.
+///
+/// The view can be bound to signals with `center_signal`, `zoom_signal` and `bounds_signal`.
+/// Changing them moves the map, and they are updated whenever the map finishes moving. They are
+/// separate props so that `center`, `zoom` and `bounds` keep taking plain values for the initial
+/// view, and existing code keeps compiling.
 #[component]
 pub fn MapContainer(
     #[prop(into, optional)] class: Signal<String>,
@@ -23,24 +30,76 @@ pub fn MapContainer(
     /// Zoom level of the map. Defaults to 10.0
     #[prop(optional, default = 10.0)]
     zoom: f64,
+    /// Two-way bound center of the map, takes precedence over `center`.
+    #[prop(optional)]
+    center_signal: Option<RwSignal<Position>>,
+    /// Two-way bound zoom level of the map, takes precedence over `zoom`.
+    #[prop(optional)]
+    zoom_signal: Option<RwSignal<f64>>,
+    /// Two-way bound visible bounds of the map, fitted initially when no center is given.
+    #[prop(optional)]
+    bounds_signal: Option<RwSignal<Bounds>>,
+    /// Coordinate reference system of the map. Defaults to `Crs::Epsg3857`.
+    #[prop(into, optional)]
+    crs: Option<Crs>,
     /// Wether zoom controls should be added to the map.
     #[prop(optional, default = true)]
     zoom_control: bool,
-    /// Wether mouse wheel zoom controls is enabled or disabled.
-    #[prop(optional, default = true)]
-    scroll_wheel_zoom: bool,
+    /// Initial bounds the map is fitted to, used when no center is given.
+    #[prop(into, optional)]
+    bounds: Option<Bounds>,
+    /// Wether mouse wheel zoom controls is enabled or disabled. Can be toggled at runtime.
+    #[prop(into, optional, default = true.into())]
+    scroll_wheel_zoom: Signal<bool>,
     /// Zoom snap of the map. Defaults to 1.0
     #[prop(optional, default = 1.0)]
     zoom_snap: f64,
     /// Zoom delta of the map. Defaults to 1.0
     #[prop(optional, default = 1.0)]
     zoom_delta: f64,
-    /// Allow zoom on double_click
-    #[prop(optional, default = true)]
-    double_click_zoom: bool,
+    /// Allow zoom on double_click. Can be toggled at runtime.
+    #[prop(into, optional, default = true.into())]
+    double_click_zoom: Signal<bool>,
+    /// Wether the map is draggable with mouse/touch. Can be toggled at runtime.
+    #[prop(into, optional, default = true.into())]
+    dragging: Signal<bool>,
+    /// Wether the map is navigable with the keyboard arrows and +/- keys. Can be toggled at runtime.
+    #[prop(into, optional, default = true.into())]
+    keyboard: Signal<bool>,
+    /// Wether the map can be zoomed to a rectangle drawn with shift pressed. Can be toggled at
+    /// runtime.
+    #[prop(into, optional, default = true.into())]
+    box_zoom: Signal<bool>,
+    /// Wether the map can be zoomed by touch-dragging with two fingers. Can be toggled at runtime.
+    #[prop(into, optional, default = true.into())]
+    touch_zoom: Signal<bool>,
     /// Sets the minimum zoom level
     #[prop(optional, default = 0.0)]
     min_zoom: f64,
+    /// Sets the maximum zoom level, defaults to the highest zoom of the layers.
+    #[prop(optional)]
+    max_zoom: Option<f64>,
+    /// Restricts the view to the given bounds.
+    #[prop(into, optional)]
+    max_bounds: Option<Bounds>,
+    /// How solid the `max_bounds` are when dragging, from 0.0 to 1.0.
+    #[prop(optional)]
+    max_bounds_viscosity: Option<f64>,
+    /// Wether the map keeps moving with momentum after dragging.
+    #[prop(optional, default = true)]
+    inertia: bool,
+    /// Wether the map jumps back to the original world copy when panned to another one.
+    #[prop(optional)]
+    world_copy_jump: bool,
+    /// Wether zoom is animated.
+    #[prop(optional, default = true)]
+    zoom_animation: bool,
+    /// Wether tile fade animation is enabled.
+    #[prop(optional, default = true)]
+    fade_animation: bool,
+    /// Wether the attribution control is added to the map.
+    #[prop(optional, default = true)]
+    attribution_control: bool,
     /// Use geolocation from the browser to track the user
     #[prop(optional)]
     locate: bool,
@@ -86,17 +145,65 @@ pub fn MapContainer(
             let options = leaflet::MapOptions::new();
             options.set_prefer_canvas(prefer_canvas);
             options.set_zoom_control(zoom_control);
-            options.set_scroll_wheel_zoom(scroll_wheel_zoom);
-            options.set_zoom(zoom);
+            options.set_scroll_wheel_zoom(scroll_wheel_zoom.get_untracked());
+            let initial_center = center_signal.map(|center| center.get_untracked()).or(center);
+            options.set_zoom(zoom_signal.map_or(zoom, |zoom| zoom.get_untracked()));
             options.set_zoom_snap(zoom_snap);
             options.set_zoom_delta(zoom_delta);
-            options.set_double_click_zoom(JsValue::from_bool(double_click_zoom));
+            options.set_double_click_zoom(JsValue::from_bool(double_click_zoom.get_untracked()));
+            options.set_dragging(dragging.get_untracked());
+            options.set_keyboard(keyboard.get_untracked());
+            options.set_box_zoom(box_zoom.get_untracked());
+            options.set_touch_zoom(JsValue::from_bool(touch_zoom.get_untracked()));
             options.set_min_zoom(min_zoom);
-            if let Some(center) = center {
+            if let Some(max_zoom) = max_zoom {
+                options.set_max_zoom(max_zoom);
+            }
+            if let Some(max_bounds) = max_bounds {
+                options.set_max_bounds(max_bounds.as_lat_lng_bounds());
+            }
+            if let Some(max_bounds_viscosity) = max_bounds_viscosity {
+                options.set_max_bounds_viscosity(max_bounds_viscosity);
+            }
+            options.set_inertia(inertia);
+            options.set_world_copy_jump(world_copy_jump);
+            options.set_zoom_animation(zoom_animation);
+            options.set_fade_animation(fade_animation);
+            options.set_attribution_control(attribution_control);
+            if let Some(center) = initial_center {
                 options.set_center(center.as_lat_lng());
             }
+            if let Some(crs) = crs.as_ref() {
+                match crs.to_leaflet() {
+                    Some(crs) => {
+                        let _ = js_sys::Reflect::set(&options, &JsValue::from_str("crs"), &crs);
+                    }
+                    None => error!("Could not create the CRS {:?}, using the default one.", crs),
+                }
+            }
             let leaflet_map = Map::new(&html_node.id(), &options);
 
+            // Without a center the view can only come from the bounds
+            let initial_bounds = bounds_signal
+                .map(|bounds| bounds.get_untracked())
+                .or(bounds);
+            if let (None, Some(bounds)) = (initial_center, initial_bounds) {
+                leaflet_map.fit_bounds(&bounds.as_lat_lng_bounds());
+            }
+
+            // Write the view back to the bound signals once the map settles
+            if center_signal.is_some() || zoom_signal.is_some() || bounds_signal.is_some() {
+                let move_map = leaflet_map.clone();
+                leaflet_map.on_move_end(Box::new(move |_| {
+                    sync_view_signals(&move_map, center_signal, zoom_signal, bounds_signal);
+                }));
+                let zoom_map = leaflet_map.clone();
+                leaflet_map.on_zoom_end(Box::new(move |_| {
+                    sync_view_signals(&zoom_map, center_signal, zoom_signal, bounds_signal);
+                }));
+                sync_view_signals(&leaflet_map, center_signal, zoom_signal, bounds_signal);
+            }
+
             // Setup events
             events.setup(&leaflet_map);
             popup_events.setup(&leaflet_map);
@@ -117,7 +224,66 @@ pub fn MapContainer(
         };
     });
 
+    // Programmatic view changes, values already matching the map are ignored so that
+    // writes coming from `sync_view_signals` don't move the map again.
+    let view_stop = Effect::watch(
+        move || {
+            (
+                center_signal.map(|center| center.get()),
+                zoom_signal.map(|zoom| zoom.get()),
+            )
+        },
+        move |(center, zoom), _, _| {
+            if let Some(map) = map_context.map_untracked() {
+                apply_view(&map, *center, *zoom);
+            }
+        },
+        false,
+    );
+
+    let bounds_stop = Effect::watch(
+        move || bounds_signal.map(|bounds| bounds.get()),
+        move |bounds, _, _| {
+            if let (Some(map), Some(bounds)) = (map_context.map_untracked(), bounds) {
+                if map_loaded(&map) && Bounds::from(map.get_bounds()).approx_eq(bounds) {
+                    return;
+                }
+                map.fit_bounds(&bounds.as_lat_lng_bounds());
+            }
+        },
+        false,
+    );
+
+    let handlers_stop = Effect::watch(
+        move || {
+            (
+                dragging.get(),
+                keyboard.get(),
+                box_zoom.get(),
+                touch_zoom.get(),
+                scroll_wheel_zoom.get(),
+                double_click_zoom.get() && !map_context.double_click_zoom_suspended(),
+            )
+        },
+        move |&(dragging, keyboard, box_zoom, touch_zoom, scroll_wheel_zoom, double_click_zoom),
+              _,
+              _| {
+            if let Some(map) = map_context.map_untracked() {
+                set_handler_enabled(&map.dragging(), dragging);
+                set_handler_enabled(&map.keyboard(), keyboard);
+                set_handler_enabled(&map.box_zoom(), box_zoom);
+                set_handler_enabled(&map.touch_zoom(), touch_zoom);
+                set_handler_enabled(&map.scroll_wheel_zoom(), scroll_wheel_zoom);
+                set_handler_enabled(&map.double_click_zoom(), double_click_zoom);
+            }
+        },
+        false,
+    );
+
     on_cleanup(move || {
+        view_stop.stop();
+        bounds_stop.stop();
+        handlers_stop.stop();
         if let Some(map) = map_context.map_untracked().as_ref() {
             map.remove();
         };
@@ -126,6 +292,72 @@ pub fn MapContainer(
     view! { <div class=move || class.get() node_ref=map_ref style=move || style.get()>{children.map(|child|child())}</div>}
 }
 
+/// Returns true once the map has a view, before that Leaflet throws when reading it.
+pub(crate) fn map_loaded(map: &Map) -> bool {
+    js_sys::Reflect::get(map, &JsValue::from_str("_loaded"))
+        .map(|loaded| loaded.is_truthy())
+        .unwrap_or(false)
+}
+
+/// Enables or disables a map interaction handler.
+fn set_handler_enabled(handler: &Handler, enabled: bool) {
+    match enabled {
+        true => handler.enable(),
+        false => handler.disable(),
+    };
+}
+
+/// Moves the map to the given center and zoom, unless it is already there.
+fn apply_view(map: &Map, center: Option<Position>, zoom: Option<f64>) {
+    if center.is_none() && zoom.is_none() {
+        return;
+    }
+    if !map_loaded(map) {
+        if let Some(center) = center {
+            map.set_view(&center.as_lat_lng(), zoom.unwrap_or_else(|| map.get_zoom()));
+        }
+        return;
+    }
+    let current_center = Position::from(map.get_center());
+    let current_zoom = map.get_zoom();
+    let center = center.unwrap_or(current_center);
+    let zoom = zoom.unwrap_or(current_zoom);
+    if center.approx_eq(&current_center) && (zoom - current_zoom).abs() <= f64::EPSILON {
+        return;
+    }
+    map.set_view(&center.as_lat_lng(), zoom);
+}
+
+/// Copies the current view of the map into the bound signals.
+fn sync_view_signals(
+    map: &Map,
+    center_signal: Option<RwSignal<Position>>,
+    zoom_signal: Option<RwSignal<f64>>,
+    bounds_signal: Option<RwSignal<Bounds>>,
+) {
+    if !map_loaded(map) {
+        return;
+    }
+    if let Some(center_signal) = center_signal {
+        let center = Position::from(map.get_center());
+        if !center_signal.get_untracked().approx_eq(&center) {
+            center_signal.set(center);
+        }
+    }
+    if let Some(zoom_signal) = zoom_signal {
+        let zoom = map.get_zoom();
+        if (zoom_signal.get_untracked() - zoom).abs() > f64::EPSILON {
+            zoom_signal.set(zoom);
+        }
+    }
+    if let Some(bounds_signal) = bounds_signal {
+        let bounds = Bounds::from(map.get_bounds());
+        if !bounds_signal.get_untracked().approx_eq(&bounds) {
+            bounds_signal.set(bounds);
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct LeafletMap {
     #[cfg(not(feature = "ssr"))]
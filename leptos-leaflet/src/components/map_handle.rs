@@ -0,0 +1,438 @@
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use leaflet::{FitBoundsOptions, Map, Point};
+use leptos::prelude::set_timeout;
+
+use super::map_container::map_loaded;
+use super::{use_leaflet_context, Bounds, LeafletMapContext, Position};
+use crate::core::{call_method, LeafletEventListener};
+
+/// Errors returned by the [`MapHandle`] when the map can't be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapError {
+    /// The map has not been created yet, or we are not running on the client.
+    NotReady,
+    /// The map exists but has no view yet, a center and zoom must be set first.
+    NoView,
+    /// Another move of the map started before the animation finished.
+    Interrupted,
+    /// The map was removed before the animation finished.
+    Disposed,
+}
+
+impl Display for MapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MapError::NotReady => write!(f, "map is not ready"),
+            MapError::NoView => write!(f, "map has no view yet"),
+            MapError::Interrupted => write!(f, "map animation was interrupted"),
+            MapError::Disposed => write!(f, "map was removed"),
+        }
+    }
+}
+
+impl std::error::Error for MapError {}
+
+/// A typed handle to the Leaflet map of a `MapContainer`.
+///
+/// All the methods work with [`Position`] and [`Bounds`] instead of the raw Leaflet types, and
+/// return a [`MapError`] instead of panicking when the map is not available yet.
+///
+/// Use [`use_map`] to get the handle from any child of a `MapContainer`.
+#[derive(Debug, Clone, Copy)]
+pub struct MapHandle {
+    context: LeafletMapContext,
+}
+
+impl MapHandle {
+    /// Creates a new handle for the given map context.
+    pub fn new(context: LeafletMapContext) -> Self {
+        Self { context }
+    }
+
+    /// Returns true once the map has been created.
+    ///
+    /// This is tracked, so it can be used to wait for the map inside effects.
+    pub fn is_ready(&self) -> bool {
+        self.context.map().is_some()
+    }
+
+    /// Returns the underlying `leaflet::Map`.
+    pub fn map(&self) -> Result<Map, MapError> {
+        self.context.map_untracked().ok_or(MapError::NotReady)
+    }
+
+    /// Returns the underlying `leaflet::Map` if it already has a view.
+    fn map_with_view(&self) -> Result<Map, MapError> {
+        let map = self.map()?;
+        if map_loaded(&map) {
+            Ok(map)
+        } else {
+            Err(MapError::NoView)
+        }
+    }
+
+    /// Sets the view of the map to the given center and zoom.
+    pub fn set_view(&self, center: Position, zoom: f64) -> Result<(), MapError> {
+        self.map()?.set_view(&center.as_lat_lng(), zoom);
+        Ok(())
+    }
+
+    /// Sets the zoom of the map.
+    pub fn set_zoom(&self, zoom: f64) -> Result<(), MapError> {
+        self.map_with_view()?.set_zoom(zoom);
+        Ok(())
+    }
+
+    /// Sets the view of the map with a smooth pan-zoom animation.
+    pub fn fly_to(&self, center: Position, zoom: f64) -> Result<(), MapError> {
+        self.map_with_view()?.fly_to(&center.as_lat_lng(), zoom);
+        Ok(())
+    }
+
+    /// Fits the map to the given bounds with a smooth pan-zoom animation.
+    pub fn fly_to_bounds(&self, bounds: Bounds) -> Result<(), MapError> {
+        self.map_with_view()?
+            .fly_to_bounds(&bounds.as_lat_lng_bounds());
+        Ok(())
+    }
+
+    /// Pans the map to the given center.
+    pub fn pan_to(&self, center: Position) -> Result<(), MapError> {
+        self.map_with_view()?.pan_to(&center.as_lat_lng());
+        Ok(())
+    }
+
+    /// Pans the map by the given number of pixels.
+    pub fn pan_by(&self, x: f64, y: f64) -> Result<(), MapError> {
+        self.map_with_view()?.pan_by(&Point::new(x, y));
+        Ok(())
+    }
+
+    /// Sets the view of the map to contain the given bounds with the maximum zoom possible.
+    ///
+    /// # Arguments
+    ///
+    /// * `bounds` - The bounds to fit.
+    /// * `padding` - Optional padding in pixels, applied to all the sides of the map.
+    pub fn fit_bounds(&self, bounds: Bounds, padding: Option<(f64, f64)>) -> Result<(), MapError> {
+        let map = self.map()?;
+        match padding {
+            Some((x, y)) => {
+                let options = FitBoundsOptions::new();
+                options.set_padding(Point::new(x, y));
+                map.fit_bounds_with_options(&bounds.as_lat_lng_bounds(), &options);
+            }
+            None => {
+                map.fit_bounds(&bounds.as_lat_lng_bounds());
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the center of the map.
+    pub fn center(&self) -> Result<Position, MapError> {
+        Ok(self.map_with_view()?.get_center().into())
+    }
+
+    /// Returns the zoom of the map.
+    pub fn zoom(&self) -> Result<f64, MapError> {
+        Ok(self.map_with_view()?.get_zoom())
+    }
+
+    /// Returns the visible bounds of the map.
+    pub fn bounds(&self) -> Result<Bounds, MapError> {
+        Ok(self.map_with_view()?.get_bounds().into())
+    }
+
+    /// Projects a position to pixel coordinates for the given zoom, or the current zoom.
+    pub fn project(&self, position: Position, zoom: Option<f64>) -> Result<(f64, f64), MapError> {
+        let map = self.map_with_view()?;
+        let zoom = zoom.unwrap_or_else(|| map.get_zoom());
+        let point = map.project(&position.as_lat_lng(), zoom);
+        Ok((point.x(), point.y()))
+    }
+
+    /// Unprojects pixel coordinates to a position for the given zoom, or the current zoom.
+    pub fn unproject(&self, point: (f64, f64), zoom: Option<f64>) -> Result<Position, MapError> {
+        let map = self.map_with_view()?;
+        let zoom = zoom.unwrap_or_else(|| map.get_zoom());
+        Ok(map.unproject(&Point::new(point.0, point.1), zoom).into())
+    }
+
+    /// Checks if the map container size changed and updates the map accordingly.
+    pub fn invalidate_size(&self) -> Result<(), MapError> {
+        self.map()?.invalidate_size(true);
+        Ok(())
+    }
+
+    /// Same as [`MapHandle::fly_to`], but returns a future resolving when the animation ends.
+    pub fn fly_to_async(&self, center: Position, zoom: f64) -> MapAnimation {
+        self.animate(move |map| {
+            map.fly_to(&center.as_lat_lng(), zoom);
+        })
+    }
+
+    /// Same as [`MapHandle::fly_to_bounds`], but returns a future resolving when the animation
+    /// ends.
+    pub fn fly_to_bounds_async(&self, bounds: Bounds) -> MapAnimation {
+        self.animate(move |map| {
+            map.fly_to_bounds(&bounds.as_lat_lng_bounds());
+        })
+    }
+
+    /// Same as [`MapHandle::pan_to`], but returns a future resolving when the animation ends.
+    pub fn pan_to_async(&self, center: Position) -> MapAnimation {
+        self.animate(move |map| {
+            map.pan_to(&center.as_lat_lng());
+        })
+    }
+
+    /// Same as [`MapHandle::fit_bounds`], but returns a future resolving when the animation
+    /// ends.
+    pub fn fit_bounds_async(&self, bounds: Bounds, padding: Option<(f64, f64)>) -> MapAnimation {
+        let handle = *self;
+        self.animate(move |_| {
+            let _ = handle.fit_bounds(bounds, padding);
+        })
+    }
+
+    /// Starts a move of the map and tracks it until Leaflet fires `moveend`.
+    ///
+    /// A running pan or fly animation is stopped before listening, so its `moveend` is not taken
+    /// for the end of this move. See [`MoveTracker`] for how the events are matched to this move.
+    fn animate(&self, start: impl FnOnce(&Map)) -> MapAnimation {
+        let map = match self.map_with_view() {
+            Ok(map) => map,
+            Err(error) => return MapAnimation::finished(Err(error)),
+        };
+        call_method(&map, "stop", &[]);
+        let state = Rc::new(RefCell::new(AnimationState::default()));
+        let tracker = Rc::new(RefCell::new(MoveTracker::default()));
+
+        let move_start_tracker = tracker.clone();
+        let move_start_state = Rc::downgrade(&state);
+        let move_end_tracker = tracker.clone();
+        let move_end_state = Rc::downgrade(&state);
+        let unload_state = Rc::downgrade(&state);
+        state.borrow_mut().listeners = vec![
+            LeafletEventListener::new(&map, "movestart", move |_: leaflet::Event| {
+                let result = move_start_tracker.borrow_mut().move_start();
+                if let Some(result) = result {
+                    finish_animation(&move_start_state, result);
+                }
+            }),
+            LeafletEventListener::new(&map, "moveend", move |_: leaflet::Event| {
+                let result = move_end_tracker.borrow().move_end();
+                if let Some(result) = result {
+                    finish_animation(&move_end_state, result);
+                }
+            }),
+            LeafletEventListener::new(&map, "unload", move |_: leaflet::Event| {
+                finish_animation(&unload_state, Err(MapError::Disposed));
+            }),
+        ];
+
+        tracker.borrow_mut().starting = true;
+        start(&map);
+        tracker.borrow_mut().starting = false;
+        MapAnimation { state }
+    }
+}
+
+/// Matches the `movestart` and `moveend` events of the map to one move.
+///
+/// Only the first `movestart` belongs to the move, a later one starts another move and
+/// interrupts it. A `moveend` before that first `movestart` ends another move, like a zoom
+/// animation that can't be stopped, unless it is fired while starting the move: Leaflet fires a
+/// lone `moveend` when asked to move to where the map already is.
+#[derive(Debug, Default)]
+struct MoveTracker {
+    /// Set while the move is being started.
+    starting: bool,
+    started: bool,
+}
+
+impl MoveTracker {
+    /// Returns the result of the move if the `movestart` ends it.
+    fn move_start(&mut self) -> Option<Result<(), MapError>> {
+        if self.started {
+            return Some(Err(MapError::Interrupted));
+        }
+        self.started = true;
+        None
+    }
+
+    /// Returns the result of the move if the `moveend` belongs to it.
+    fn move_end(&self) -> Option<Result<(), MapError>> {
+        (self.started || self.starting).then_some(Ok(()))
+    }
+}
+
+/// A future resolving when a map animation started by the [`MapHandle`] finishes.
+///
+/// The animation starts right away, awaiting the future is only needed to know when it ends.
+/// It resolves with [`MapError::Interrupted`] if another move of the map starts first, and with
+/// [`MapError::Disposed`] if the `MapContainer` is cleaned up. Starting another animation with
+/// the [`MapHandle`] stops this one first. Dropping the future stops tracking
+/// the animation, without stopping it.
+pub struct MapAnimation {
+    state: Rc<RefCell<AnimationState>>,
+}
+
+#[derive(Default)]
+struct AnimationState {
+    result: Option<Result<(), MapError>>,
+    waker: Option<Waker>,
+    listeners: Vec<LeafletEventListener>,
+}
+
+impl MapAnimation {
+    fn finished(result: Result<(), MapError>) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(AnimationState {
+                result: Some(result),
+                ..Default::default()
+            })),
+        }
+    }
+}
+
+impl Future for MapAnimation {
+    type Output = Result<(), MapError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+        match state.result {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Stores the result of the animation and wakes up the task awaiting it.
+fn finish_animation(state: &Weak<RefCell<AnimationState>>, result: Result<(), MapError>) {
+    let Some(state) = state.upgrade() else {
+        return;
+    };
+    let (waker, listeners) = {
+        let mut state = state.borrow_mut();
+        if state.result.is_some() {
+            return;
+        }
+        state.result = Some(result);
+        (state.waker.take(), std::mem::take(&mut state.listeners))
+    };
+    // We are running inside one of the listeners, so they can only be removed afterwards
+    set_timeout(move || drop(listeners), Duration::ZERO);
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+/// Returns a [`MapHandle`] for the map of the closest `MapContainer`.
+///
+/// # Panics
+///
+/// Panics if called outside of a `MapContainer`.
+pub fn use_map() -> MapHandle {
+    MapHandle::new(use_leaflet_context().expect("use_map must be used within a MapContainer"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy)]
+    enum MoveEvent {
+        Start,
+        End,
+    }
+
+    /// The moves listening to the events of one map, with their results.
+    #[derive(Default)]
+    struct Moves(Vec<(MoveTracker, Option<Result<(), MapError>>)>);
+
+    impl Moves {
+        /// Starts tracking a move, with the events Leaflet fires while starting it.
+        fn start(&mut self, events: &[MoveEvent]) -> usize {
+            let index = self.0.len();
+            self.0.push((MoveTracker::default(), None));
+            self.0[index].0.starting = true;
+            for event in events {
+                self.fire(*event);
+            }
+            self.0[index].0.starting = false;
+            index
+        }
+
+        /// Fires an event to every move still listening, a finished move stops listening.
+        fn fire(&mut self, event: MoveEvent) {
+            for (tracker, result) in self.0.iter_mut().filter(|(_, result)| result.is_none()) {
+                *result = match event {
+                    MoveEvent::Start => tracker.move_start(),
+                    MoveEvent::End => tracker.move_end(),
+                };
+            }
+        }
+
+        fn result(&self, index: usize) -> Option<Result<(), MapError>> {
+            self.0[index].1
+        }
+    }
+
+    #[test]
+    fn test_chained_pans_end_on_their_own_moveend() {
+        let mut moves = Moves::default();
+        let first = moves.start(&[MoveEvent::Start]);
+        // The second pan stops the first one before listening to the map
+        moves.fire(MoveEvent::End);
+        let second = moves.start(&[MoveEvent::Start]);
+        assert_eq!(moves.result(first), Some(Ok(())));
+        assert_eq!(moves.result(second), None);
+
+        moves.fire(MoveEvent::End);
+        assert_eq!(moves.result(second), Some(Ok(())));
+    }
+
+    #[test]
+    fn test_moveend_before_own_movestart_is_ignored() {
+        // Zoom animations start on the next frame and can't be stopped
+        let mut moves = Moves::default();
+        let first = moves.start(&[]);
+        moves.fire(MoveEvent::Start);
+        let second = moves.start(&[]);
+        moves.fire(MoveEvent::End);
+        assert_eq!(moves.result(first), Some(Ok(())));
+        assert_eq!(moves.result(second), None);
+
+        moves.fire(MoveEvent::Start);
+        moves.fire(MoveEvent::End);
+        assert_eq!(moves.result(second), Some(Ok(())));
+    }
+
+    #[test]
+    fn test_later_movestart_interrupts() {
+        let mut moves = Moves::default();
+        let first = moves.start(&[MoveEvent::Start]);
+        // A move started by the user
+        moves.fire(MoveEvent::Start);
+        assert_eq!(moves.result(first), Some(Err(MapError::Interrupted)));
+    }
+
+    #[test]
+    fn test_move_to_current_view_ends_right_away() {
+        let mut moves = Moves::default();
+        let first = moves.start(&[MoveEvent::End]);
+        assert_eq!(moves.result(first), Some(Ok(())));
+    }
+}
@@ -1,21 +1,99 @@
 use crate::components::context::extend_context_with_overlay;
 use crate::components::position::Position;
+use leptos::html::Div;
 use leptos::prelude::*;
 use wasm_bindgen::JsCast;
 
+use super::marker_animation::{
+    interpolate_angle, interpolate_position, rotate_transform, AnimationFrames,
+};
 use super::{
-    use_pane_context, DragEvents, LayerEvents, LeafletMapContext, MouseEvents, MoveEvents,
-    PopupEvents, TooltipEvents,
+    use_layer_parent, use_pane_context, DragEvents, HtmlIcon, ImageIcon, LayerEvents,
+    MarkerAnimation, MarkerIcon, MouseEvents, MoveEvents, PopupEvents, TooltipEvents,
 };
-use crate::core::{JsSignal, JsStoredValue};
+use crate::core::{JsSignal, JsStoredValue, LeafletEventListener};
 use crate::{setup_layer_leaflet_option, setup_layer_leaflet_string};
 
+/// Content of the icon of a [`Marker`], rendered in a `DivIcon`.
+///
+/// The view stays reactive while shown on the map. The class, size and anchor of the icon are the
+/// ones of an `icon` set to [`MarkerIcon::Html`], or `icon_class`, `icon_size` and `icon_anchor`.
+/// Without a size, the icon takes the size of its content.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use leptos::prelude::*;
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn Station(count: Signal<u32>) -> impl IntoView {
+///     view! {
+///         <Marker
+///             position=position!(51.505, -0.09)
+///             icon_class="station".to_string()
+///             icon_anchor=Some((12.0, 12.0))
+///         >
+///             <DivIconContent slot>
+///                 <span class="badge">{move || count.get()}</span>
+///             </DivIconContent>
+///         </Marker>
+///     }
+/// }
+/// ```
+#[slot]
+pub struct DivIconContent {
+    children: ChildrenFn,
+}
+
+/// When a [`Marker`] updates its `bind_position` while being dragged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DragUpdate {
+    /// On every move of the marker.
+    #[default]
+    Drag,
+    /// Once the marker is dropped.
+    DragEnd,
+}
+
 /// A marker component.
+///
+/// A draggable marker can write its position back to a signal with `bind_position`, and keep it
+/// in place with `drag_constraint`:
+///
+/// ```rust,no_run
+/// use leptos::prelude::*;
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn Pin() -> impl IntoView {
+///     let position = RwSignal::new(position!(51.505, -0.09));
+///     let area = Bounds::new(position!(51.51, -0.08), position!(51.5, -0.1));
+///     view! {
+///         <Marker
+///             bind_position=position
+///             draggable=true
+///             drag_constraint=move |position: Position| area.clamp(position)
+///         />
+///         <p>{move || format!("{:?}", position.get())}</p>
+///     }
+/// }
+/// ```
 #[component(transparent)]
 pub fn Marker(
     /// Position for the Marker
-    #[prop(into)]
+    #[prop(into, optional)]
     position: JsSignal<Position>,
+    /// Two-way bound position, updated when the marker is dragged. Takes precedence over
+    /// `position`, which is ignored when both are set.
+    #[prop(optional)]
+    bind_position: Option<RwSignal<Position>>,
+    /// When `bind_position` is updated while dragging.
+    #[prop(optional)]
+    bind_update: DragUpdate,
+    /// Applied to the position while dragging, to snap it or keep it in an area.
+    #[prop(into, optional)]
+    drag_constraint: Option<Callback<Position, Position>>,
     #[prop(into, optional)] draggable: Signal<bool>,
     #[prop(into, optional)] keyboard: Signal<Option<bool>>,
     #[prop(into, optional)] title: Signal<String>,
@@ -31,32 +109,71 @@ pub fn Marker(
     #[prop(into, optional)] auto_pan: Signal<Option<bool>>,
     #[prop(into, optional)] auto_pan_padding: Signal<Option<(f64, f64)>>,
     #[prop(into, optional)] auto_pan_speed: Signal<Option<f64>>,
+    /// The icon of the marker, takes precedence over the other `icon_*` props.
+    #[prop(into, optional)]
+    icon: Option<Signal<MarkerIcon>>,
     #[prop(into, optional)] icon_class: Signal<Option<String>>,
     #[prop(into, optional)] icon_url: Signal<Option<String>>,
     #[prop(into, optional)] icon_size: Signal<Option<(f64, f64)>>,
     #[prop(into, optional)] icon_anchor: Signal<Option<(f64, f64)>>,
     #[prop(into, optional)] attribution: Signal<String>,
     #[prop(into, optional)] rotation: Signal<Option<f64>>,
+    /// Animates the marker between the values of `position`, instead of moving it at once.
+    #[prop(optional)]
+    animate: Option<MarkerAnimation>,
     #[prop(into, optional)] move_events: MoveEvents,
     #[prop(into, optional)] mouse_events: MouseEvents,
     #[prop(into, optional)] drag_events: DragEvents,
     #[prop(into, optional)] layer_events: LayerEvents,
     #[prop(into, optional)] popup_events: PopupEvents,
     #[prop(into, optional)] tooltip_events: TooltipEvents,
+    /// A view used as the icon, takes precedence over `icon_url` and `icon_class`.
+    #[prop(optional)]
+    div_icon_content: Option<DivIconContent>,
     #[prop(optional)] children: Option<Children>,
 ) -> impl IntoView {
     let position_tracking = position;
-    let icon_class_tracking = icon_class;
-    let icon_url_tracking = icon_url;
-    let icon_size_tracking = icon_size;
-    let icon_anchor_tracking = icon_anchor;
-    let map_context = use_context::<LeafletMapContext>().expect("Map context not found");
+    let layer_parent = use_layer_parent();
 
     let overlay_context = extend_context_with_overlay();
     let overlay = JsStoredValue::new_local(None::<leaflet::Marker>);
+    let drag_listeners = JsStoredValue::new_local(Vec::<LeafletEventListener>::new());
+    let move_listener = JsStoredValue::new_local(None::<LeafletEventListener>);
+    let position_frames = AnimationFrames::new();
+    let rotation_frames = AnimationFrames::new();
+    let shown_rotation = JsStoredValue::new_local(None::<f64>);
+
+    // Render the icon content to an element, which Leaflet moves into the icon
+    let icon_content = NodeRef::<Div>::new();
+    let has_icon_content = div_icon_content.is_some();
+
+    let marker_icon = Signal::derive(move || {
+        let html_icon = || HtmlIcon {
+            class_name: icon_class.get(),
+            size: icon_size.get(),
+            anchor: icon_anchor.get(),
+            ..Default::default()
+        };
+        if let Some(icon) = icon {
+            icon.get()
+        } else if has_icon_content {
+            MarkerIcon::Html(html_icon())
+        } else if let Some(icon_url) = icon_url.get() {
+            MarkerIcon::Image(ImageIcon {
+                url: icon_url,
+                size: icon_size.get(),
+                anchor: icon_anchor.get(),
+                ..Default::default()
+            })
+        } else if icon_class.with(Option::is_some) {
+            MarkerIcon::Html(html_icon())
+        } else {
+            MarkerIcon::Default
+        }
+    });
 
     Effect::new(move |_| {
-        if let Some(map) = map_context.map() {
+        if layer_parent.is_ready() {
             let options = leaflet::MarkerOptions::new();
             let drag = draggable.get_untracked();
             if drag {
@@ -88,32 +205,46 @@ pub fn Marker(
             if let Some((x, y)) = auto_pan_padding.get_untracked() {
                 options.set_auto_pan_padding(leaflet::Point::new(x, y));
             }
-            if let Some(icon_url) = icon_url.get_untracked() {
-                let icon_options = leaflet::IconOptions::new();
-                icon_options.set_icon_url(icon_url);
-                if let Some((x, y)) = icon_size.get_untracked() {
-                    icon_options.set_icon_size(leaflet::Point::new(x, y));
-                }
-                if let Some((x, y)) = icon_anchor.get_untracked() {
-                    icon_options.set_icon_anchor(leaflet::Point::new(x, y));
-                }
-                let icon = leaflet::Icon::new(&icon_options);
+            let content = icon_content.get_untracked().filter(|_| has_icon_content);
+            if let Some(icon) = marker_icon.get_untracked().to_leaflet(content.as_deref()) {
                 options.set_icon(icon);
-            } else if let Some(icon_class) = icon_class.get_untracked() {
-                let icon_options = leaflet::DivIconOptions::new();
-                icon_options.set_class_name(icon_class);
-                if let Some((x, y)) = icon_size.get_untracked() {
-                    icon_options.set_icon_size(leaflet::Point::new(x, y));
-                }
-                if let Some((x, y)) = icon_anchor.get_untracked() {
-                    icon_options.set_icon_anchor(leaflet::Point::new(x, y));
-                }
-                let icon = leaflet::DivIcon::new(&icon_options);
-                options.set_icon(icon.into());
             }
 
+            let initial_position = match bind_position {
+                Some(bind_position) => bind_position.get_untracked(),
+                None => position.get_untracked(),
+            };
             let marker =
-                leaflet::Marker::new_with_options(&position.get_untracked().as_lat_lng(), &options);
+                leaflet::Marker::new_with_options(&initial_position.as_lat_lng(), &options);
+
+            if bind_position.is_some() || drag_constraint.is_some() {
+                let drag_marker = marker.clone();
+                let drag_end_marker = marker.clone();
+                drag_listeners.set_value(vec![
+                    LeafletEventListener::new(&marker, "drag", move |_: leaflet::Event| {
+                        let position = constrain_position(&drag_marker, drag_constraint);
+                        if bind_update == DragUpdate::Drag {
+                            update_bound_position(bind_position, position);
+                        }
+                    }),
+                    LeafletEventListener::new(&marker, "dragend", move |_: leaflet::Event| {
+                        let position = Position::from(drag_end_marker.get_lat_lng());
+                        update_bound_position(bind_position, position);
+                    }),
+                ]);
+            }
+
+            // Leaflet rewrites the transform of the icon on every move, dropping the rotation
+            let rotated_marker = marker.clone();
+            move_listener.set_value(Some(LeafletEventListener::new(
+                &marker,
+                "move",
+                move |_: leaflet::Event| {
+                    if let Some(rotation) = shown_rotation.get_value() {
+                        set_rotation(&rotated_marker, rotation);
+                    }
+                },
+            )));
 
             mouse_events.setup(&marker);
             move_events.setup(&marker);
@@ -122,55 +253,53 @@ pub fn Marker(
             tooltip_events.setup(&marker);
             layer_events.setup(&marker);
 
-            marker.add_to(&map);
+            layer_parent.add_layer(&marker);
             overlay_context.set_container(&marker);
             overlay.set_value(Some(marker));
         };
     });
 
     let position_stop = Effect::watch(
-        move || position_tracking.get(),
-        move |position_tracking, _, _| {
-            if let Some(marker) = overlay.get_value().as_ref() {
-                marker.set_lat_lng(&position_tracking.as_lat_lng());
+        move || bind_position.is_none().then(|| position_tracking.get()),
+        move |&position, _, _| {
+            let (Some(position), Some(marker)) = (position, overlay.get_value()) else {
+                return;
+            };
+            match animate {
+                // From where the marker is shown, which may be in the middle of an animation
+                Some(animation) => {
+                    let from = Position::from(marker.get_lat_lng());
+                    position_frames.run(animation, move |progress| {
+                        let position = interpolate_position(from, position, progress);
+                        marker.set_lat_lng(&position.as_lat_lng());
+                    });
+                }
+                None => marker.set_lat_lng(&position.as_lat_lng()),
             }
         },
         false,
     );
 
-    let icon_stop = Effect::watch(
-        move || {
-            (
-                icon_url_tracking.get(),
-                icon_class_tracking.get(),
-                icon_size_tracking.get(),
-                icon_anchor_tracking.get(),
-            )
+    let bind_position_stop = Effect::watch(
+        move || bind_position.map(|bind_position| bind_position.get()),
+        move |position, _, _| {
+            if let (Some(position), Some(marker)) = (position, overlay.get_value()) {
+                // Skips the positions written back while dragging
+                if !Position::from(marker.get_lat_lng()).approx_eq(position) {
+                    marker.set_lat_lng(&position.as_lat_lng());
+                }
+            }
         },
-        move |(maybe_icon_url, maybe_icon_class, maybe_icon_size, maybe_icon_anchor), _, _| {
+        false,
+    );
+
+    let icon_stop = Effect::watch(
+        move || marker_icon.get(),
+        move |icon, _, _| {
             if let Some(marker) = overlay.get_value() {
-                if let Some(icon_url) = maybe_icon_url {
-                    let icon_options = leaflet::IconOptions::new();
-                    icon_options.set_icon_url(icon_url.clone());
-                    if let Some((x, y)) = maybe_icon_size {
-                        icon_options.set_icon_size(leaflet::Point::new(*x, *y));
-                    }
-                    if let Some((x, y)) = maybe_icon_anchor {
-                        icon_options.set_icon_anchor(leaflet::Point::new(*x, *y));
-                    }
-                    let icon = leaflet::Icon::new(&icon_options);
+                let content = icon_content.get_untracked().filter(|_| has_icon_content);
+                if let Some(icon) = icon.to_leaflet_or_default(content.as_deref()) {
                     marker.set_icon(&icon);
-                } else if let Some(icon_class) = maybe_icon_class {
-                    let icon_options = leaflet::DivIconOptions::new();
-                    icon_options.set_class_name(icon_class.clone());
-                    if let Some((x, y)) = maybe_icon_size {
-                        icon_options.set_icon_size(leaflet::Point::new(*x, *y));
-                    }
-                    if let Some((x, y)) = maybe_icon_anchor {
-                        icon_options.set_icon_anchor(leaflet::Point::new(*x, *y));
-                    }
-                    let icon = leaflet::DivIcon::new(&icon_options);
-                    marker.set_icon(&icon.into());
                 }
             }
         },
@@ -205,37 +334,25 @@ pub fn Marker(
     let rotation_stop = Effect::watch(
         move || rotation.get(),
         move |&rotation, prev_rotation, _| {
-            if let (Some(marker), Some(rotation)) = (overlay.get_value().as_ref(), rotation) {
+            if let (Some(marker), Some(rotation)) = (overlay.get_value(), rotation) {
                 if Some(rotation.trunc()) == prev_rotation.copied().flatten().map(|r| r.trunc()) {
                     return;
                 }
-                if let Ok(internal_icon) = js_sys::Reflect::get(marker, &"_icon".into()) {
-                    let internal_icon = internal_icon.unchecked_ref::<web_sys::HtmlElement>();
-                    _ = internal_icon
-                        .style()
-                        .set_property("--gps_rotation", &format!("{}deg", rotation));
-
-                    let transform = internal_icon
-                        .style()
-                        .get_property_value("transform")
-                        .unwrap_or_default();
-
-                    let transform = match transform.contains("rotate(") {
-                        true => transform
-                            .split_whitespace()
-                            .map(|part| match part.starts_with("rotate(") {
-                                true => format!("rotate({}deg)", rotation),
-                                false => part.to_string(),
-                            })
-                            .collect::<Vec<String>>()
-                            .join(" "),
-                        false => format!("{} rotate({}deg)", transform, rotation),
-                    };
-
-                    let _ = internal_icon.style().set_property("transform", &transform);
-                    let _ = internal_icon
-                        .style()
-                        .set_property("transform-origin", "center");
+                match (
+                    animate.filter(|animate| animate.rotate),
+                    shown_rotation.get_value(),
+                ) {
+                    (Some(animation), Some(from)) => {
+                        rotation_frames.run(animation, move |progress| {
+                            let rotation = interpolate_angle(from, rotation, progress);
+                            set_rotation(&marker, rotation);
+                            shown_rotation.set_value(Some(rotation));
+                        });
+                    }
+                    _ => {
+                        set_rotation(&marker, rotation);
+                        shown_rotation.set_value(Some(rotation));
+                    }
                 }
             }
         },
@@ -244,14 +361,76 @@ pub fn Marker(
 
     on_cleanup(move || {
         position_stop.stop();
+        bind_position_stop.stop();
         icon_stop.stop();
         opacity_stop.stop();
         drag_stop.stop();
         rotation_stop.stop();
+        position_frames.cancel();
+        rotation_frames.cancel();
+        drag_listeners.try_update_value(Vec::clear);
+        move_listener.try_update_value(|listener| *listener = None);
         if let Some(overlay) = overlay.get_value() {
-            overlay.remove();
+            layer_parent.remove_layer(&overlay);
         }
     });
 
-    children.map(|child| child())
+    let icon_content_view = div_icon_content.map(|content| {
+        view! {
+            <div style="visibility:collapse">
+                <div node_ref=icon_content>{(content.children)()}</div>
+            </div>
+        }
+    });
+
+    (children.map(|child| child()), icon_content_view)
+}
+
+/// Rotates the icon of the marker by the given angle in degrees.
+fn set_rotation(marker: &leaflet::Marker, rotation: f64) {
+    if let Ok(internal_icon) = js_sys::Reflect::get(marker, &"_icon".into()) {
+        let internal_icon = internal_icon.unchecked_ref::<web_sys::HtmlElement>();
+        _ = internal_icon
+            .style()
+            .set_property("--gps_rotation", &format!("{}deg", rotation));
+
+        let transform = internal_icon
+            .style()
+            .get_property_value("transform")
+            .unwrap_or_default();
+        let transform = rotate_transform(&transform, rotation);
+
+        let _ = internal_icon.style().set_property("transform", &transform);
+        let _ = internal_icon
+            .style()
+            .set_property("transform-origin", "center");
+    }
+}
+
+/// Applies the constraint to the position of a dragged marker, and returns the position.
+fn constrain_position(
+    marker: &leaflet::Marker,
+    drag_constraint: Option<Callback<Position, Position>>,
+) -> Position {
+    let position = Position::from(marker.get_lat_lng());
+    let Some(drag_constraint) = drag_constraint else {
+        return position;
+    };
+    let constrained = drag_constraint.run(position);
+    if !constrained.approx_eq(&position) {
+        marker.set_lat_lng(&constrained.as_lat_lng());
+    }
+    constrained
+}
+
+fn update_bound_position(bind_position: Option<RwSignal<Position>>, position: Position) {
+    let Some(bind_position) = bind_position else {
+        return;
+    };
+    if bind_position
+        .try_with_untracked(|current| !current.approx_eq(&position))
+        .unwrap_or_default()
+    {
+        bind_position.try_set(position);
+    }
 }
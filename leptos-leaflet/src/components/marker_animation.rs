@@ -0,0 +1,221 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+use super::Position;
+use crate::core::JsStoredValue;
+
+/// Easing of a [`MarkerAnimation`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    /// Maps the linear progress of an animation, from 0 to 1, to the eased progress.
+    pub fn apply(&self, progress: f64) -> f64 {
+        let t = progress.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// Animates the moves of a [`Marker`](super::Marker) when its position changes.
+///
+/// A new position while animating starts a new animation from where the marker is.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use leptos::prelude::*;
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn Vehicle(position: Signal<Position>, heading: Signal<Option<f64>>) -> impl IntoView {
+///     let animation = MarkerAnimation::new(Duration::from_secs(2))
+///         .with_easing(Easing::Linear)
+///         .with_rotation();
+///     view! { <Marker position=position rotation=heading animate=animation /> }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarkerAnimation {
+    pub duration: Duration,
+    pub easing: Easing,
+    /// Whether the `rotation` of the marker is animated too, along the shortest angle.
+    pub rotate: bool,
+}
+
+impl MarkerAnimation {
+    /// Creates an animation of the given duration, with the default easing.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            easing: Easing::default(),
+            rotate: false,
+        }
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Animates the rotation too.
+    pub fn with_rotation(mut self) -> Self {
+        self.rotate = true;
+        self
+    }
+}
+
+impl Default for MarkerAnimation {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(1))
+    }
+}
+
+/// The position at `progress` of the straight move between two positions.
+pub(crate) fn interpolate_position(from: Position, to: Position, progress: f64) -> Position {
+    Position::new(
+        from.lat + (to.lat - from.lat) * progress,
+        from.lng + (to.lng - from.lng) * progress,
+    )
+}
+
+/// The angle in degrees at `progress` of the rotation between two angles, along the shortest way.
+pub(crate) fn interpolate_angle(from: f64, to: f64, progress: f64) -> f64 {
+    let delta = (to - from + 180.0).rem_euclid(360.0) - 180.0;
+    from + delta * progress
+}
+
+/// Sets the rotation of a CSS `transform`, replacing the rotation if it has one.
+pub(crate) fn rotate_transform(transform: &str, rotation: f64) -> String {
+    match transform.contains("rotate(") {
+        true => transform
+            .split_whitespace()
+            .map(|part| match part.starts_with("rotate(") {
+                true => format!("rotate({}deg)", rotation),
+                false => part.to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join(" "),
+        false => format!("{} rotate({}deg)", transform, rotation),
+    }
+}
+
+/// Runs animations on animation frames, a new one interrupting the running one.
+#[derive(Clone, Copy)]
+pub(crate) struct AnimationFrames {
+    handle: JsStoredValue<Option<AnimationFrameRequestHandle>>,
+}
+
+impl AnimationFrames {
+    pub(crate) fn new() -> Self {
+        Self {
+            handle: JsStoredValue::new_local(None),
+        }
+    }
+
+    /// Stops the running animation, if any.
+    pub(crate) fn cancel(&self) {
+        if let Some(handle) = self.handle.try_update_value(Option::take).flatten() {
+            handle.cancel();
+        }
+    }
+
+    /// Calls `apply` with the eased progress on every frame until the animation ends.
+    pub(crate) fn run(&self, animation: MarkerAnimation, apply: impl Fn(f64) + 'static) {
+        self.cancel();
+        let start = js_sys::Date::now();
+        self.request_frame(start, animation, Rc::new(apply));
+    }
+
+    fn request_frame(&self, start: f64, animation: MarkerAnimation, apply: Rc<dyn Fn(f64)>) {
+        let frames = *self;
+        let handle = request_animation_frame_with_handle(move || {
+            let duration = animation.duration.as_secs_f64() * 1000.0;
+            let progress = if duration > 0.0 {
+                (js_sys::Date::now() - start) / duration
+            } else {
+                1.0
+            };
+            apply(animation.easing.apply(progress));
+            if progress < 1.0 {
+                frames.request_frame(start, animation, apply);
+            } else {
+                frames.handle.try_set_value(None);
+            }
+        });
+        self.handle.try_set_value(handle.ok());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_easing_bounds() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            assert_eq!(easing.apply(1.5), 1.0);
+        }
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+        assert!(Easing::EaseIn.apply(0.25) < 0.25);
+        assert!(Easing::EaseOut.apply(0.25) > 0.25);
+    }
+
+    #[test]
+    fn test_interpolate_position() {
+        let position =
+            interpolate_position(Position::new(10.0, 20.0), Position::new(20.0, 0.0), 0.25);
+        assert_eq!(position, Position::new(12.5, 15.0));
+    }
+
+    #[test]
+    fn test_interpolate_angle_shortest_way() {
+        // Through north instead of all the way around
+        assert_eq!(interpolate_angle(350.0, 10.0, 0.5), 360.0);
+        assert_eq!(interpolate_angle(10.0, 350.0, 0.5), 0.0);
+        assert_eq!(interpolate_angle(0.0, 90.0, 0.5), 45.0);
+        assert_eq!(interpolate_angle(90.0, -90.0, 1.0), -90.0);
+    }
+
+    #[test]
+    fn test_rotate_transform_after_move() {
+        let rotated = rotate_transform("translate3d(10px, 20px, 0px)", 45.0);
+        assert_eq!(rotated, "translate3d(10px, 20px, 0px) rotate(45deg)");
+        assert_eq!(
+            rotate_transform(&rotated, 90.0),
+            "translate3d(10px, 20px, 0px) rotate(90deg)"
+        );
+        // Moving the marker rewrites the transform, the rotation is applied again on top of it
+        assert_eq!(
+            rotate_transform("translate3d(15px, 25px, 0px)", 90.0),
+            "translate3d(15px, 25px, 0px) rotate(90deg)"
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use js_sys::{Array, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::core::{get_function, leaflet_namespace, set_option};
+
+/// The icon of a [`Marker`](super::Marker).
+///
+/// It's a plain value, so the same icon can be shared by many markers, and changed reactively.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use leptos::prelude::*;
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn Stops(stops: Vec<Position>) -> impl IntoView {
+///     let icon = MarkerIcon::Image(
+///         ImageIcon::new("/stop.png")
+///             .with_retina_url("/stop@2x.png")
+///             .with_size((24.0, 24.0))
+///             .with_anchor((12.0, 24.0))
+///             .with_popup_anchor((0.0, -24.0)),
+///     );
+///     stops
+///         .into_iter()
+///         .map(|stop| view! { <Marker position=stop icon=icon.clone() /> })
+///         .collect_view()
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum MarkerIcon {
+    /// The default Leaflet marker.
+    #[default]
+    Default,
+    /// An image, like `L.Icon`.
+    Image(ImageIcon),
+    /// A `div` element with HTML content, like `L.DivIcon`.
+    Html(HtmlIcon),
+}
+
+impl MarkerIcon {
+    /// Creates the Leaflet icon, `None` for the default one, which Leaflet uses when not set.
+    ///
+    /// When `content` is set, it's shown in a `DivIcon` instead, with the options of an `Html`
+    /// icon if any.
+    pub(crate) fn to_leaflet(
+        &self,
+        content: Option<&web_sys::HtmlElement>,
+    ) -> Option<leaflet::Icon> {
+        match (self, content) {
+            (_, Some(content)) => {
+                let html_icon = match self {
+                    MarkerIcon::Html(html_icon) => html_icon.clone(),
+                    _ => HtmlIcon::default(),
+                };
+                Some(html_icon.to_leaflet(Some(content)).into())
+            }
+            (MarkerIcon::Default, None) => None,
+            (MarkerIcon::Image(image_icon), None) => Some(image_icon.to_leaflet()),
+            (MarkerIcon::Html(html_icon), None) => Some(html_icon.to_leaflet(None).into()),
+        }
+    }
+
+    /// Creates the Leaflet icon, including the default one, to replace the icon of a marker.
+    pub(crate) fn to_leaflet_or_default(
+        &self,
+        content: Option<&web_sys::HtmlElement>,
+    ) -> Option<leaflet::Icon> {
+        self.to_leaflet(content).or_else(default_icon)
+    }
+}
+
+impl From<ImageIcon> for MarkerIcon {
+    fn from(image_icon: ImageIcon) -> Self {
+        MarkerIcon::Image(image_icon)
+    }
+}
+
+impl From<HtmlIcon> for MarkerIcon {
+    fn from(html_icon: HtmlIcon) -> Self {
+        MarkerIcon::Html(html_icon)
+    }
+}
+
+/// An image icon, with all the options of `L.Icon`.
+///
+/// Sizes and anchors are in pixels, anchors being relative to the top left corner of the icon.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImageIcon {
+    pub url: String,
+    /// Image used on high resolution screens.
+    pub retina_url: Option<String>,
+    pub size: Option<(f64, f64)>,
+    /// Point of the icon placed on the position of the marker.
+    pub anchor: Option<(f64, f64)>,
+    /// Point from which popups open, relative to `anchor`.
+    pub popup_anchor: Option<(f64, f64)>,
+    /// Point from which tooltips open, relative to `anchor`.
+    pub tooltip_anchor: Option<(f64, f64)>,
+    pub shadow_url: Option<String>,
+    pub shadow_retina_url: Option<String>,
+    pub shadow_size: Option<(f64, f64)>,
+    /// Same as `anchor` if not set.
+    pub shadow_anchor: Option<(f64, f64)>,
+    /// Class of both the icon and shadow images.
+    pub class_name: Option<String>,
+}
+
+impl ImageIcon {
+    /// Creates an icon showing the image at `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_retina_url(mut self, retina_url: impl Into<String>) -> Self {
+        self.retina_url = Some(retina_url.into());
+        self
+    }
+
+    pub fn with_size(mut self, size: (f64, f64)) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn with_anchor(mut self, anchor: (f64, f64)) -> Self {
+        self.anchor = Some(anchor);
+        self
+    }
+
+    pub fn with_popup_anchor(mut self, popup_anchor: (f64, f64)) -> Self {
+        self.popup_anchor = Some(popup_anchor);
+        self
+    }
+
+    pub fn with_tooltip_anchor(mut self, tooltip_anchor: (f64, f64)) -> Self {
+        self.tooltip_anchor = Some(tooltip_anchor);
+        self
+    }
+
+    pub fn with_shadow_url(mut self, shadow_url: impl Into<String>) -> Self {
+        self.shadow_url = Some(shadow_url.into());
+        self
+    }
+
+    pub fn with_shadow_retina_url(mut self, shadow_retina_url: impl Into<String>) -> Self {
+        self.shadow_retina_url = Some(shadow_retina_url.into());
+        self
+    }
+
+    pub fn with_shadow_size(mut self, shadow_size: (f64, f64)) -> Self {
+        self.shadow_size = Some(shadow_size);
+        self
+    }
+
+    pub fn with_shadow_anchor(mut self, shadow_anchor: (f64, f64)) -> Self {
+        self.shadow_anchor = Some(shadow_anchor);
+        self
+    }
+
+    pub fn with_class_name(mut self, class_name: impl Into<String>) -> Self {
+        self.class_name = Some(class_name.into());
+        self
+    }
+
+    fn to_leaflet(&self) -> leaflet::Icon {
+        let options = leaflet::IconOptions::new();
+        options.set_icon_url(self.url.clone());
+        if let Some(retina_url) = &self.retina_url {
+            options.set_icon_retina_url(retina_url.clone());
+        }
+        if let Some((x, y)) = self.size {
+            options.set_icon_size(leaflet::Point::new(x, y));
+        }
+        if let Some((x, y)) = self.anchor {
+            options.set_icon_anchor(leaflet::Point::new(x, y));
+        }
+        if let Some((x, y)) = self.popup_anchor {
+            options.set_popup_anchor(leaflet::Point::new(x, y));
+        }
+        if let Some((x, y)) = self.tooltip_anchor {
+            options.set_tooltip_anchor(leaflet::Point::new(x, y));
+        }
+        if let Some(shadow_url) = &self.shadow_url {
+            options.set_shadow_url(shadow_url.clone());
+        }
+        if let Some(shadow_retina_url) = &self.shadow_retina_url {
+            options.set_shadow_retina_url(shadow_retina_url.clone());
+        }
+        if let Some((x, y)) = self.shadow_size {
+            options.set_shadow_size(leaflet::Point::new(x, y));
+        }
+        if let Some((x, y)) = self.shadow_anchor {
+            options.set_shadow_anchor(leaflet::Point::new(x, y));
+        }
+        if let Some(class_name) = &self.class_name {
+            options.set_class_name(class_name.clone());
+        }
+        leaflet::Icon::new(&options)
+    }
+}
+
+/// A `div` icon with HTML content, with the options of `L.DivIcon`.
+///
+/// Without a `class_name`, Leaflet styles the icon as a white square.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HtmlIcon {
+    pub html: String,
+    pub class_name: Option<String>,
+    pub size: Option<(f64, f64)>,
+    /// Point of the icon placed on the position of the marker.
+    pub anchor: Option<(f64, f64)>,
+    /// Point from which popups open, relative to `anchor`.
+    pub popup_anchor: Option<(f64, f64)>,
+    /// Point from which tooltips open, relative to `anchor`.
+    pub tooltip_anchor: Option<(f64, f64)>,
+}
+
+impl HtmlIcon {
+    /// Creates an icon showing the given HTML.
+    pub fn new(html: impl Into<String>) -> Self {
+        Self {
+            html: html.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_class_name(mut self, class_name: impl Into<String>) -> Self {
+        self.class_name = Some(class_name.into());
+        self
+    }
+
+    pub fn with_size(mut self, size: (f64, f64)) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn with_anchor(mut self, anchor: (f64, f64)) -> Self {
+        self.anchor = Some(anchor);
+        self
+    }
+
+    pub fn with_popup_anchor(mut self, popup_anchor: (f64, f64)) -> Self {
+        self.popup_anchor = Some(popup_anchor);
+        self
+    }
+
+    pub fn with_tooltip_anchor(mut self, tooltip_anchor: (f64, f64)) -> Self {
+        self.tooltip_anchor = Some(tooltip_anchor);
+        self
+    }
+
+    /// Creates the `DivIcon`, showing `content` instead of the HTML if set.
+    fn to_leaflet(&self, content: Option<&web_sys::HtmlElement>) -> leaflet::DivIcon {
+        let options = leaflet::DivIconOptions::new();
+        if let Some(class_name) = &self.class_name {
+            options.set_class_name(class_name.clone());
+        }
+        if let Some((x, y)) = self.anchor {
+            options.set_icon_anchor(leaflet::Point::new(x, y));
+        }
+        if let Some((x, y)) = self.popup_anchor {
+            options.set_popup_anchor(leaflet::Point::new(x, y));
+        }
+        if let Some((x, y)) = self.tooltip_anchor {
+            set_option(&options, "tooltipAnchor", &leaflet::Point::new(x, y));
+        }
+        match content {
+            Some(content) => {
+                // Sized by the content unless set
+                let size = self
+                    .size
+                    .map_or(JsValue::NULL, |(x, y)| leaflet::Point::new(x, y).into());
+                set_option(&options, "iconSize", &size);
+                // Leaflet appends an element passed as `html` to the icon
+                set_option(&options, "html", content);
+            }
+            None => {
+                if let Some((x, y)) = self.size {
+                    options.set_icon_size(leaflet::Point::new(x, y));
+                }
+                options.set_html(self.html.clone());
+            }
+        }
+        leaflet::DivIcon::new(&options)
+    }
+}
+
+/// Creates an instance of `L.Icon.Default`.
+fn default_icon() -> Option<leaflet::Icon> {
+    let icon = Reflect::get(&leaflet_namespace()?, &JsValue::from_str("Icon")).ok()?;
+    let default = get_function(&icon, "Default")?;
+    Reflect::construct(&default, &Array::new())
+        .ok()
+        .map(JsCast::unchecked_into)
+}
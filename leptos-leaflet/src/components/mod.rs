@@ -1,11 +1,23 @@
 mod bounds;
 mod circle;
+mod circle_marker;
 mod context;
 mod control;
+mod crs;
+mod distortable_image_overlay;
+mod draw;
+mod draw_control;
 mod events;
+mod geo_json;
 mod image_overlay;
+mod layer_group;
+mod layers_control;
 mod map_container;
+mod map_handle;
 mod marker;
+mod marker_animation;
+mod marker_icon;
+mod open_state;
 mod pane;
 
 mod path_options;
@@ -14,23 +26,39 @@ mod polyline;
 mod popup;
 mod position;
 mod quad_tile_layer;
+mod rectangle;
+mod shape_editor;
+mod svg_overlay;
 mod tile_layer;
 mod tile_layer_wms;
 mod tooltip;
 mod video_overlay;
+mod view_signals;
 mod zoom;
 
 pub use bounds::Bounds;
 pub use circle::Circle;
+pub use circle_marker::CircleMarker;
 pub use context::*;
 pub use control::Control;
+pub use crs::{Crs, CustomCrs, Proj4Crs, Transformation};
+pub use distortable_image_overlay::{DistortableImageOverlay, ImageCorners};
+pub use draw::{use_draw, DrawHandle, DrawTool, DrawnShape};
+pub use draw_control::DrawControl;
 pub use events::{
-    DragEvents, LayerEvents, MapEvents, MouseEvents, MoveEvents, PopupEvents, TooltipEvents,
+    DragEvents, LayerEvents, LayersControlEvent, LayersControlEvents, MapEvents, MouseEvents,
+    MoveEvents, PopupEvents, TooltipEvents, VideoError, VideoEvents,
 };
+pub use geo_json::GeoJson;
 pub use image_overlay::ImageOverlay;
+pub use layer_group::{FeatureGroup, LayerGroup};
+pub use layers_control::{BaseLayer, LayersControl, Overlay};
 pub use leaflet::{CircleOptions, PathOptions, PolylineOptions};
 pub use map_container::{LeafletMap, MapContainer};
-pub use marker::Marker;
+pub use map_handle::{use_map, MapAnimation, MapError, MapHandle};
+pub use marker::{DivIconContent, DragUpdate, Marker};
+pub use marker_animation::{Easing, MarkerAnimation};
+pub use marker_icon::{HtmlIcon, ImageIcon, MarkerIcon};
 pub use pane::{
     provide_pane_context, provide_pane_context_with_renderer, use_pane_context, Pane, PaneContext,
     PaneRendererScope, PaneStrategy,
@@ -42,10 +70,16 @@ pub use polyline::Polyline;
 pub use popup::Popup;
 pub use position::*;
 pub use quad_tile_layer::QuadTileLayer;
+pub use rectangle::Rectangle;
+pub use svg_overlay::SvgOverlay;
 pub use tile_layer::TileLayer;
 pub use tile_layer_wms::{TileLayerWms, TileLayerWmsEvents};
 pub use tooltip::Tooltip;
-pub use video_overlay::VideoOverlay;
+pub use video_overlay::{VideoOverlay, VideoPlayback};
+pub use view_signals::{
+    use_map_bounds, use_map_bounds_with_rate, use_map_center, use_map_center_with_rate,
+    use_map_zoom, use_map_zoom_with_rate, ViewUpdateRate,
+};
 pub use zoom::Zoom;
 
 #[macro_export]
@@ -0,0 +1,112 @@
+use js_sys::Reflect;
+use leptos::prelude::*;
+use wasm_bindgen::prelude::*;
+
+use crate::core::{call_method, JsStoredValue, LeafletEventListener};
+
+/// The kind of a `DivOverlay`, naming its open and close methods and events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OverlayKind {
+    Popup,
+    Tooltip,
+}
+
+impl OverlayKind {
+    fn name(&self) -> &'static str {
+        match self {
+            OverlayKind::Popup => "popup",
+            OverlayKind::Tooltip => "tooltip",
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            OverlayKind::Popup => "Popup",
+            OverlayKind::Tooltip => "Tooltip",
+        }
+    }
+}
+
+/// What a popup or tooltip opens on: the layer it's bound to, or the map when standalone.
+#[derive(Clone)]
+pub(crate) enum OverlaySource {
+    Layer(leaflet::Layer),
+    Map(leaflet::Map),
+}
+
+impl OverlaySource {
+    fn evented(&self) -> &JsValue {
+        match self {
+            OverlaySource::Layer(layer) => layer.as_ref(),
+            OverlaySource::Map(map) => map.as_ref(),
+        }
+    }
+
+    /// Opens a new `overlay` if `open` is set, and keeps `open` in sync with the user opening or
+    /// closing it.
+    pub(crate) fn bind_open(
+        &self,
+        kind: OverlayKind,
+        overlay: &JsValue,
+        open: Option<RwSignal<bool>>,
+        listeners: JsStoredValue<Vec<LeafletEventListener>>,
+    ) {
+        let Some(open) = open else {
+            return;
+        };
+        listeners.set_value(self.listen_open(kind, overlay, open));
+        if open.get_untracked() {
+            self.set_open(kind, overlay, true);
+        }
+    }
+
+    /// Keeps `open` in sync with the open and close events of `overlay`.
+    ///
+    /// Both the layer and the map fire them, with the overlay as a property of the event.
+    fn listen_open(
+        &self,
+        kind: OverlayKind,
+        overlay: &JsValue,
+        open: RwSignal<bool>,
+    ) -> Vec<LeafletEventListener> {
+        let listener = |suffix: &str, value: bool| {
+            let overlay = overlay.clone();
+            LeafletEventListener::new(
+                self.evented(),
+                &format!("{}{suffix}", kind.name()),
+                move |event: JsValue| {
+                    let source = Reflect::get(&event, &JsValue::from_str(kind.name()));
+                    if source.is_ok_and(|source| source == overlay)
+                        && open.try_get_untracked() != Some(value)
+                    {
+                        open.try_set(value);
+                    }
+                },
+            )
+        };
+        vec![listener("open", true), listener("close", false)]
+    }
+
+    /// Opens or closes `overlay`, unless it's already in that state.
+    pub(crate) fn set_open(&self, kind: OverlayKind, overlay: &JsValue, open: bool) {
+        let is_open =
+            call_method(overlay, "isOpen", &[]).is_some_and(|is_open| is_open.is_truthy());
+        if is_open == open {
+            return;
+        }
+        match (self, open) {
+            (OverlaySource::Layer(layer), true) => {
+                call_method(layer, &format!("open{}", kind.title()), &[]);
+            }
+            (OverlaySource::Layer(layer), false) => {
+                call_method(layer, &format!("close{}", kind.title()), &[]);
+            }
+            (OverlaySource::Map(map), true) => {
+                call_method(overlay, "openOn", &[map.clone().into()]);
+            }
+            (OverlaySource::Map(_), false) => {
+                call_method(overlay, "remove", &[]);
+            }
+        }
+    }
+}
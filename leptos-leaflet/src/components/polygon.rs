@@ -1,11 +1,13 @@
 use leptos::prelude::*;
 
+use js_sys::Array;
 use leaflet::{to_lat_lng_array, PolylineOptions};
 
+use super::shape_editor::edit_vertices;
 use super::{
-    extend_context_with_overlay, update_overlay_context, use_pane_context, FillRule, LayerEvents,
-    LeafletMapContext, LineCap, LineJoin, MouseEvents, PaneRendererScope, PopupEvents, Position,
-    StringEmptyOption, TooltipEvents,
+    extend_context_with_overlay, update_overlay_context, use_layer_parent, use_pane_context,
+    FillRule, LayerEvents, LineCap, LineJoin, MouseEvents, PaneRendererScope, PolygonRings,
+    PopupEvents, Position, StringEmptyOption, TooltipEvents,
 };
 use crate::core::JsStoredValue;
 use crate::{
@@ -14,9 +16,34 @@ use crate::{
 use tracing::debug;
 
 /// A polygon overlay that represents a polygon on the map.
+///
+/// The shape is given either by `positions`, with optional `holes`, or by `multi_positions` for
+/// polygons made of several parts. `multi_positions` takes precedence when it is not empty.
+///
+/// With `editable`, handles let the user drag, insert and delete the vertices of the outer ring,
+/// written to the signal which the polygon then follows. Only simple polygons can be edited, the
+/// handles are hidden while `holes` or `multi_positions` are not empty.
+///
+/// ```rust,no_run
+/// use leptos::prelude::*;
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn Zone(zone: RwSignal<Vec<Position>>, editing: Signal<bool>) -> impl IntoView {
+///     view! { <Polygon editable=zone editing=editing /> }
+/// }
+/// ```
 #[component(transparent)]
 pub fn Polygon(
-    #[prop(into)] positions: Signal<Vec<Position>>,
+    /// The outer ring of the polygon.
+    #[prop(into, optional)]
+    positions: Signal<Vec<Position>>,
+    /// Holes cut out of the polygon, like courtyards.
+    #[prop(into, optional)]
+    holes: Signal<Vec<Vec<Position>>>,
+    /// The parts of a multi-polygon, each one with its own holes.
+    #[prop(into, optional)]
+    multi_positions: Signal<Vec<PolygonRings>>,
     #[prop(into, optional)] stroke: Signal<Option<bool>>,
     #[prop(into, optional)] color: Signal<String>,
     #[prop(into, optional)] weight: Signal<Option<f64>>,
@@ -38,21 +65,40 @@ pub fn Polygon(
     #[prop(into, optional)] layer_events: LayerEvents,
     #[prop(into, optional)] popup_events: PopupEvents,
     #[prop(into, optional)] tooltip_events: TooltipEvents,
+    /// Outer ring edited with handles on the map. Takes precedence over `positions`, which is
+    /// ignored when both are set.
+    #[prop(optional)]
+    editable: Option<RwSignal<Vec<Position>>>,
+    /// The fewest vertices left when deleting them.
+    #[prop(optional, default = 3)]
+    min_vertices: usize,
+    /// Shows the edit handles of an editable shape.
+    #[prop(into, optional, default = true.into())]
+    editing: Signal<bool>,
     #[prop(optional)] children: Option<ChildrenFn>,
 ) -> impl IntoView {
     extend_context_with_overlay();
+    let layer_parent = use_layer_parent();
     let overlay = JsStoredValue::new_local(None::<leaflet::Polygon>);
+    let positions = editable.map_or(positions, Signal::from);
+    if let Some(editable) = editable {
+        let editing = Signal::derive(move || {
+            editing.get() && holes.with(Vec::is_empty) && multi_positions.with(Vec::is_empty)
+        });
+        edit_vertices(editable, true, min_vertices, editing);
+    }
 
     let positions_for_effect = positions;
     let color_clone = color;
     let fill_color_clone = fill_color;
     // This effect just setups the polygon when we get a map
     Effect::new(move |_| {
-        if let Some(map) = use_context::<LeafletMapContext>()
-            .expect("map context")
-            .map()
-        {
-            let lat_lngs = to_lat_lng_array(&positions.get_untracked());
+        if layer_parent.is_ready() {
+            let lat_lngs = polygon_lat_lngs(
+                &positions.get_untracked(),
+                &holes.get_untracked(),
+                &multi_positions.get_untracked(),
+            );
             let options = PolylineOptions::new();
             setup_layer_leaflet_option!(stroke, options);
             setup_layer_leaflet_string!(color, options);
@@ -113,17 +159,23 @@ pub fn Polygon(
             popup_events.setup(&polygon);
             tooltip_events.setup(&polygon);
 
-            polygon.add_to(&map);
+            layer_parent.add_layer(&polygon);
             update_overlay_context(&polygon);
             overlay.set_value(Some(polygon));
         }
     });
 
     let position_stop = Effect::watch(
-        move || positions_for_effect.get(),
-        move |pos, _, _| {
+        move || {
+            (
+                positions_for_effect.get(),
+                holes.get(),
+                multi_positions.get(),
+            )
+        },
+        move |(positions, holes, multi_positions), _, _| {
             if let Some(polygon) = overlay.get_value().as_ref() {
-                let lat_lngs = to_lat_lng_array(pos);
+                let lat_lngs = polygon_lat_lngs(positions, holes, multi_positions);
                 polygon.set_lat_lngs(&lat_lngs);
             }
         },
@@ -228,9 +280,27 @@ pub fn Polygon(
         weight_stop.stop();
         smooth_factor_stop.stop();
         if let Some(overlay) = overlay.try_get_value().flatten().as_ref() {
-            overlay.remove();
+            layer_parent.remove_layer(overlay);
         }
     });
 
     children.map(|child| child())
 }
+
+/// Builds the `LatLng` arrays of the polygon, nested when it has holes or several parts.
+fn polygon_lat_lngs(
+    positions: &[Position],
+    holes: &[Vec<Position>],
+    multi_positions: &[PolygonRings],
+) -> Array {
+    if !multi_positions.is_empty() {
+        multi_positions
+            .iter()
+            .map(PolygonRings::to_lat_lngs)
+            .collect()
+    } else if !holes.is_empty() {
+        PolygonRings::with_holes(positions.to_vec(), holes.to_vec()).to_lat_lngs()
+    } else {
+        to_lat_lng_array(positions)
+    }
+}
@@ -1,21 +1,36 @@
+use js_sys::Array;
 use leaflet::{to_lat_lng_array, PolylineOptions};
 use leptos::prelude::*;
 
+use super::shape_editor::edit_vertices;
 use super::{
-    extend_context_with_overlay, update_overlay_context, use_pane_context, FillRule, LayerEvents,
-    LeafletMapContext, LineCap, LineJoin, MouseEvents, PaneRendererScope, PopupEvents, Position,
-    StringEmptyOption, TooltipEvents,
+    extend_context_with_overlay, update_overlay_context, use_layer_parent, use_pane_context,
+    FillRule, LayerEvents, LineCap, LineJoin, MouseEvents, PaneRendererScope, PopupEvents,
+    Position, StringEmptyOption, TooltipEvents,
 };
-use crate::core::JsStoredValue;
+use crate::core::{call_method, get_function, JsStoredValue};
 use crate::{
     setup_layer_leaflet_option, setup_layer_leaflet_option_ref, setup_layer_leaflet_string,
 };
 use tracing::debug;
+use wasm_bindgen::JsValue;
 
 /// A polyline overlay that represents a polyline on the map.
+///
+/// When the positions only grow, like a live track, the new points are appended to the existing
+/// line instead of rebuilding it.
+///
+/// With `editable`, handles let the user drag, insert and delete the points, written to the
+/// signal which the line then follows. Multi-polylines can't be edited, the handles are hidden
+/// while `multi_positions` is not empty.
 #[component(transparent)]
 pub fn Polyline(
-    #[prop(into)] positions: Signal<Vec<Position>>,
+    /// The points of the line.
+    #[prop(into, optional)]
+    positions: Signal<Vec<Position>>,
+    /// The parts of a multi-polyline, takes precedence over `positions` when not empty.
+    #[prop(into, optional)]
+    multi_positions: Signal<Vec<Vec<Position>>>,
     #[prop(into, optional)] stroke: Signal<Option<bool>>,
     #[prop(into, optional)] color: Signal<String>,
     #[prop(into, optional)] weight: Signal<Option<f64>>,
@@ -37,20 +52,36 @@ pub fn Polyline(
     #[prop(into, optional)] layer_events: LayerEvents,
     #[prop(into, optional)] popup_events: PopupEvents,
     #[prop(into, optional)] tooltip_events: TooltipEvents,
+    /// Points edited with handles on the map. Takes precedence over `positions`, which is
+    /// ignored when both are set.
+    #[prop(optional)]
+    editable: Option<RwSignal<Vec<Position>>>,
+    /// The fewest points left when deleting them.
+    #[prop(optional, default = 2)]
+    min_vertices: usize,
+    /// Shows the edit handles of an editable shape.
+    #[prop(into, optional, default = true.into())]
+    editing: Signal<bool>,
     #[prop(optional)] children: Option<Children>,
 ) -> impl IntoView {
     extend_context_with_overlay();
+    let layer_parent = use_layer_parent();
     let overlay = JsStoredValue::new_local(None::<leaflet::Polyline>);
+    // The parts shown by the line, new positions are diffed against them
+    let applied_parts = StoredValue::new(Vec::<Vec<Position>>::new());
+    let positions = editable.map_or(positions, Signal::from);
+    if let Some(editable) = editable {
+        let editing = Signal::derive(move || editing.get() && multi_positions.with(Vec::is_empty));
+        edit_vertices(editable, false, min_vertices, editing);
+    }
 
     let positions_for_effect = positions;
     let color_clone = color;
     let fill_color_clone = fill_color;
     Effect::new(move |_| {
-        if let Some(map) = use_context::<LeafletMapContext>()
-            .expect("map context")
-            .map()
-        {
-            let lat_lngs = to_lat_lng_array(&positions.get_untracked());
+        if layer_parent.is_ready() {
+            let parts = polyline_parts(positions.get_untracked(), multi_positions.get_untracked());
+            let lat_lngs = polyline_lat_lngs(&parts);
             let options = PolylineOptions::new();
             setup_layer_leaflet_option!(stroke, options);
             setup_layer_leaflet_string!(color, options);
@@ -108,19 +139,30 @@ pub fn Polyline(
             popup_events.setup(&polyline);
             tooltip_events.setup(&polyline);
 
-            polyline.add_to(&map);
+            layer_parent.add_layer(&polyline);
             update_overlay_context(&polyline);
             overlay.set_value(Some(polyline));
+            applied_parts.set_value(parts);
         }
     });
 
     let position_stop = Effect::watch(
-        move || positions_for_effect.get(),
-        move |pos, _, _| {
-            if let Some(polygon) = overlay.get_value().as_ref() {
-                let lat_lngs = to_lat_lng_array(pos);
-                polygon.set_lat_lngs(&lat_lngs);
+        move || polyline_parts(positions_for_effect.get(), multi_positions.get()),
+        move |parts, _, _| {
+            let Some(polyline) = overlay.get_value() else {
+                return;
+            };
+            let appended = applied_parts.with_value(|applied| appended_positions(applied, parts));
+            match appended {
+                Some(appended) => {
+                    let multi = parts.len() > 1;
+                    for (part, positions) in appended {
+                        add_lat_lngs(&polyline, multi.then_some(part), positions);
+                    }
+                }
+                None => polyline.set_lat_lngs(&polyline_lat_lngs(parts)),
             }
+            applied_parts.set_value(parts.clone());
         },
         false,
     );
@@ -223,9 +265,112 @@ pub fn Polyline(
         weight_stop.stop();
         smooth_factor_stop.stop();
         if let Some(overlay) = overlay.try_get_value().flatten().as_ref() {
-            overlay.remove();
+            layer_parent.remove_layer(overlay);
         }
     });
 
     children.map(|child| child())
 }
+
+/// The parts of the line, `multi_positions` if not empty or the single `positions` part.
+fn polyline_parts(
+    positions: Vec<Position>,
+    multi_positions: Vec<Vec<Position>>,
+) -> Vec<Vec<Position>> {
+    if multi_positions.is_empty() {
+        vec![positions]
+    } else {
+        multi_positions
+    }
+}
+
+fn polyline_lat_lngs(parts: &[Vec<Position>]) -> Array {
+    match parts {
+        [positions] => to_lat_lng_array(positions),
+        parts => parts
+            .iter()
+            .map(|positions| JsValue::from(to_lat_lng_array(positions)))
+            .collect(),
+    }
+}
+
+/// Returns the positions appended at the end of each part, with the index of the part.
+///
+/// Returns `None` when the parts changed in any other way, and the line must be rebuilt.
+fn appended_positions<'a>(
+    previous: &[Vec<Position>],
+    current: &'a [Vec<Position>],
+) -> Option<Vec<(usize, &'a [Position])>> {
+    if previous.len() != current.len() {
+        return None;
+    }
+    let mut appended = Vec::new();
+    for (part, (previous, current)) in previous.iter().zip(current).enumerate() {
+        if !current.starts_with(previous) {
+            return None;
+        }
+        if current.len() > previous.len() {
+            appended.push((part, &current[previous.len()..]));
+        }
+    }
+    Some(appended)
+}
+
+/// Appends positions with `addLatLng`, to the given part of a multi-polyline if any.
+fn add_lat_lngs(polyline: &leaflet::Polyline, part: Option<usize>, positions: &[Position]) {
+    let Some(add_lat_lng) = get_function(polyline, "addLatLng") else {
+        return;
+    };
+    // The parts are the nested arrays returned by `getLatLngs`
+    let ring = match part {
+        Some(part) => call_method(polyline, "getLatLngs", &[])
+            .map(|lat_lngs| Array::from(&lat_lngs).get(part as u32))
+            .unwrap_or(JsValue::UNDEFINED),
+        None => JsValue::UNDEFINED,
+    };
+    for position in positions {
+        let _ = add_lat_lng.call2(polyline, &position.as_lat_lng(), &ring);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::positions;
+
+    #[test]
+    fn test_appended_positions() {
+        let previous = vec![positions(&[(0.0, 0.0), (1.0, 1.0)])];
+        let current = vec![positions(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)])];
+        let appended = appended_positions(&previous, &current).unwrap();
+        assert_eq!(appended, vec![(0, &current[0][2..])]);
+    }
+
+    #[test]
+    fn test_appended_positions_multi() {
+        let previous = vec![positions(&[(0.0, 0.0)]), positions(&[(5.0, 5.0)])];
+        let current = vec![
+            positions(&[(0.0, 0.0)]),
+            positions(&[(5.0, 5.0), (6.0, 6.0)]),
+        ];
+        let appended = appended_positions(&previous, &current).unwrap();
+        assert_eq!(appended, vec![(1, &current[1][1..])]);
+    }
+
+    #[test]
+    fn test_changed_positions_are_not_appended() {
+        let previous = vec![positions(&[(0.0, 0.0), (1.0, 1.0)])];
+        // Modified point
+        let modified = vec![positions(&[(0.0, 0.0), (3.0, 3.0), (2.0, 2.0)])];
+        assert_eq!(appended_positions(&previous, &modified), None);
+        // Removed point
+        let shortened = vec![positions(&[(0.0, 0.0)])];
+        assert_eq!(appended_positions(&previous, &shortened), None);
+        // Moved point
+        let moved = vec![positions(&[(0.0, 0.0), (3.0, 3.0)])];
+        assert_eq!(appended_positions(&previous, &moved), None);
+        // New part
+        let more_parts = vec![previous[0].clone(), positions(&[(2.0, 2.0)])];
+        assert_eq!(appended_positions(&previous, &more_parts), None);
+    }
+}
@@ -1,13 +1,22 @@
+use js_sys::{Object, Reflect};
 use leptos::html::Div;
 use leptos::prelude::*;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 
+use super::open_state::{OverlayKind, OverlaySource};
 use super::{use_pane_context, LeafletMapContext, PaneStrategy, Position};
-use crate::core::{IntoThreadSafeJsValue, JsSignal};
+use crate::core::{
+    call_method, IntoThreadSafeJsValue, JsSignal, JsStoredValue, LeafletEventListener,
+};
 use crate::prelude::LeafletOverlayContainerContext;
 
 /// A popup component for displaying content on the map.
 ///
+/// Changes of the options apply to the existing popup, except for the pane and `close_button`,
+/// and a standalone popup moves to its new `position`. The popup is laid out again whenever its
+/// content resizes.
+///
 /// This component supports different pane handling strategies through the `pane_strategy` parameter.
 ///
 /// # Pane Integration
@@ -110,6 +119,24 @@ use crate::prelude::LeafletOverlayContainerContext;
 ///     }
 /// }
 /// ```
+///
+/// Popup opened from app state, like selecting an item in a list:
+/// ```rust,no_run
+/// use leptos::prelude::*;
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn Place(name: String, position: Position, selected: Signal<bool>) -> impl IntoView {
+///     // Also set back to false when the user closes the popup
+///     let open = RwSignal::new(false);
+///     Effect::new(move |_| open.set(selected.get()));
+///     view! {
+///         <Marker position=position>
+///             <Popup open=open>{name}</Popup>
+///         </Marker>
+///     }
+/// }
+/// ```
 #[component]
 pub fn Popup(
     #[prop(into, optional)] position: JsSignal<Position>,
@@ -127,13 +154,35 @@ pub fn Popup(
     #[prop(into, optional)] close_on_escape_key: Option<Signal<bool>>,
     #[prop(into, optional)] close_on_click: Option<Signal<bool>>,
     #[prop(into, optional)] class_name: Option<Signal<String>>,
+    /// Whether the popup is open, set when the user opens or closes it.
+    #[prop(optional)]
+    open: Option<RwSignal<bool>>,
     children: Children,
 ) -> impl IntoView {
     let map_context = use_context::<LeafletMapContext>();
     let overlay_context = use_context::<LeafletOverlayContainerContext>();
+    let popup_source = JsStoredValue::new_local(None::<(leaflet::Popup, OverlaySource)>);
+    let open_listeners = JsStoredValue::new_local(Vec::<LeafletEventListener>::new());
 
     // Render popup content to a html element
     let content = NodeRef::<Div>::new();
+    let resize_observer = JsStoredValue::new_local(None::<ContentResizeObserver>);
+
+    // The pane and `close_button` are only read when creating the popup
+    let settings = move || PopupSettings {
+        offset: offset.map(|offset| offset.get()),
+        min_width: min_width.map(|min_width| min_width.get()),
+        max_width: max_width.map(|max_width| max_width.get()),
+        auto_pan: auto_pan.map(|auto_pan| auto_pan.get()),
+        auto_pan_padding_top_left: auto_pan_padding_top_left.map(|padding| padding.get()),
+        auto_pan_padding_bottom_right: auto_pan_padding_bottom_right.map(|padding| padding.get()),
+        auto_pan_padding: auto_pan_padding.map(|padding| padding.get()),
+        keep_in_view: keep_in_view.map(|keep_in_view| keep_in_view.get()),
+        auto_close: auto_close.map(|auto_close| auto_close.get()),
+        close_on_escape_key: close_on_escape_key
+            .map(|close_on_escape_key| close_on_escape_key.get()),
+        close_on_click: close_on_click.map(|close_on_click| close_on_click.get()),
+    };
 
     Effect::new(move |_| {
         let inner_content = content;
@@ -156,47 +205,10 @@ pub fn Popup(
                 // Use Leaflet's default pane behavior - don't set any pane
             }
         }
-        if let Some(offset) = offset {
-            let (x, y) = offset.get_untracked();
-            options.set_offset(leaflet::Point::new(f64::from(x), f64::from(y)));
-        }
-        if let Some(min_width) = min_width {
-            options.set_min_width(min_width.get_untracked());
-        }
-        if let Some(max_width) = max_width {
-            options.set_max_width(max_width.get_untracked());
-        }
-        if let Some(auto_pan) = auto_pan {
-            options.set_auto_pan(auto_pan.get_untracked());
-        }
-        if let Some(auto_pan_padding_top_left) = auto_pan_padding_top_left {
-            let (x, y) = auto_pan_padding_top_left.get_untracked();
-            options.set_auto_pan_padding_top_left(leaflet::Point::new(f64::from(x), f64::from(y)));
-        }
-        if let Some(auto_pan_padding_bottom_right) = auto_pan_padding_bottom_right {
-            let (x, y) = auto_pan_padding_bottom_right.get_untracked();
-            options
-                .set_auto_pan_padding_bottom_right(leaflet::Point::new(f64::from(x), f64::from(y)));
-        }
-        if let Some(auto_pan_padding) = auto_pan_padding {
-            let (x, y) = auto_pan_padding.get_untracked();
-            options.set_auto_pan_padding(leaflet::Point::new(f64::from(x), f64::from(y)));
-        }
-        if let Some(keep_in_view) = keep_in_view {
-            options.set_keep_in_view(keep_in_view.get_untracked());
-        }
+        untrack(settings).apply(&options);
         if let Some(close_button) = close_button {
             options.set_close_button(close_button.get_untracked());
         }
-        if let Some(auto_close) = auto_close {
-            options.set_auto_close(auto_close.get_untracked());
-        }
-        if let Some(close_on_escape_key) = close_on_escape_key {
-            options.set_close_on_escape_key(close_on_escape_key.get_untracked());
-        }
-        if let Some(close_on_click) = close_on_click {
-            options.set_close_on_click(close_on_click.get_untracked());
-        }
         if let Some(class_name) = &class_name {
             options.set_class_name(class_name.get_untracked());
         }
@@ -208,7 +220,14 @@ pub fn Popup(
                 let html_view: &JsValue = content.unchecked_ref();
                 popup.set_content(html_view);
                 marker.bind_popup(&popup);
+                let source = OverlaySource::Layer(marker);
+                source.bind_open(OverlayKind::Popup, popup.value(), open, open_listeners);
+                popup_source.set_value(Some((popup.value().clone(), source)));
+                resize_observer.set_value(ContentResizeObserver::new(&content, popup.value()));
                 on_cleanup(move || {
+                    open_listeners.try_update_value(Vec::clear);
+                    resize_observer.try_set_value(None);
+                    popup_source.try_set_value(None);
                     popup.remove();
                 });
             }
@@ -219,12 +238,187 @@ pub fn Popup(
             let content = inner_content.get_untracked().expect("content ref");
             let html_view: &JsValue = content.unchecked_ref();
             popup.set_content(html_view);
-            popup.open_on(&map);
+            if open.is_none() {
+                popup.open_on(&map);
+            }
+            let source = OverlaySource::Map(map);
+            source.bind_open(OverlayKind::Popup, popup.value(), open, open_listeners);
+            popup_source.set_value(Some((popup.value().clone(), source)));
+            resize_observer.set_value(ContentResizeObserver::new(&content, popup.value()));
             on_cleanup(move || {
+                open_listeners.try_update_value(Vec::clear);
+                resize_observer.try_set_value(None);
+                popup_source.try_set_value(None);
                 popup.remove();
             });
         }
     });
 
+    let open_stop = Effect::watch(
+        move || open.map(|open| open.get()),
+        move |open, _, _| {
+            if let (Some(open), Some((popup, source))) = (open, popup_source.get_value()) {
+                source.set_open(OverlayKind::Popup, &popup, *open);
+            }
+        },
+        false,
+    );
+
+    let settings_stop = Effect::watch(
+        settings,
+        move |settings, _, _| {
+            if let Some((popup, _)) = popup_source.get_value() {
+                let options = leaflet::PopupOptions::default();
+                settings.apply(&options);
+                update_options(&popup, &options);
+            }
+        },
+        false,
+    );
+
+    let class_name_stop = Effect::watch(
+        move || class_name.map(|class_name| class_name.get()),
+        move |class_name, previous, _| {
+            if let (Some(class_name), Some((popup, _))) = (class_name, popup_source.get_value()) {
+                let previous = previous.cloned().flatten().unwrap_or_default();
+                update_class_name(&popup, &previous, class_name);
+            }
+        },
+        false,
+    );
+
+    // A bound popup opens at its layer, only a standalone one follows `position`
+    let position_stop = Effect::watch(
+        move || position.get(),
+        move |position, _, _| {
+            if let Some((popup, OverlaySource::Map(_))) = popup_source.get_value() {
+                call_method(&popup, "setLatLng", &[position.as_lat_lng().into()]);
+            }
+        },
+        false,
+    );
+
+    on_cleanup(move || {
+        open_stop.stop();
+        settings_stop.stop();
+        class_name_stop.stop();
+        position_stop.stop();
+    });
+
     view! { <div style="visibility:collapse"><div node_ref=content>{children()}</div></div> }
 }
+
+/// The options of a popup that apply to it when they change.
+struct PopupSettings {
+    offset: Option<(i32, i32)>,
+    min_width: Option<f64>,
+    max_width: Option<f64>,
+    auto_pan: Option<bool>,
+    auto_pan_padding_top_left: Option<(i32, i32)>,
+    auto_pan_padding_bottom_right: Option<(i32, i32)>,
+    auto_pan_padding: Option<(i32, i32)>,
+    keep_in_view: Option<bool>,
+    auto_close: Option<bool>,
+    close_on_escape_key: Option<bool>,
+    close_on_click: Option<bool>,
+}
+
+impl PopupSettings {
+    fn apply(&self, options: &leaflet::PopupOptions) {
+        if let Some((x, y)) = self.offset {
+            options.set_offset(leaflet::Point::new(f64::from(x), f64::from(y)));
+        }
+        if let Some(min_width) = self.min_width {
+            options.set_min_width(min_width);
+        }
+        if let Some(max_width) = self.max_width {
+            options.set_max_width(max_width);
+        }
+        if let Some(auto_pan) = self.auto_pan {
+            options.set_auto_pan(auto_pan);
+        }
+        if let Some((x, y)) = self.auto_pan_padding_top_left {
+            options.set_auto_pan_padding_top_left(leaflet::Point::new(f64::from(x), f64::from(y)));
+        }
+        if let Some((x, y)) = self.auto_pan_padding_bottom_right {
+            options
+                .set_auto_pan_padding_bottom_right(leaflet::Point::new(f64::from(x), f64::from(y)));
+        }
+        if let Some((x, y)) = self.auto_pan_padding {
+            options.set_auto_pan_padding(leaflet::Point::new(f64::from(x), f64::from(y)));
+        }
+        if let Some(keep_in_view) = self.keep_in_view {
+            options.set_keep_in_view(keep_in_view);
+        }
+        if let Some(auto_close) = self.auto_close {
+            options.set_auto_close(auto_close);
+        }
+        if let Some(close_on_escape_key) = self.close_on_escape_key {
+            options.set_close_on_escape_key(close_on_escape_key);
+        }
+        if let Some(close_on_click) = self.close_on_click {
+            options.set_close_on_click(close_on_click);
+        }
+    }
+}
+
+/// Merges `options` into the options of the popup, then lays it out and places it again.
+fn update_options(popup: &leaflet::Popup, options: &leaflet::PopupOptions) {
+    if let Ok(current) = Reflect::get(popup, &JsValue::from_str("options")) {
+        Object::assign(current.unchecked_ref(), options.unchecked_ref());
+    }
+    call_method(popup, "update", &[]);
+}
+
+/// Replaces the `previous` classes of the popup, on its element if it's been shown already.
+fn update_class_name(popup: &leaflet::Popup, previous: &str, class_name: &str) {
+    if let Ok(options) = Reflect::get(popup, &JsValue::from_str("options")) {
+        let _ = Reflect::set(
+            &options,
+            &JsValue::from_str("className"),
+            &JsValue::from_str(class_name),
+        );
+    }
+    let Some(element) = call_method(popup, "getElement", &[])
+        .and_then(|element| element.dyn_into::<web_sys::Element>().ok())
+    else {
+        return;
+    };
+    let class_list = element.class_list();
+    for class in previous.split_whitespace() {
+        let _ = class_list.remove_1(class);
+    }
+    for class in class_name.split_whitespace() {
+        let _ = class_list.add_1(class);
+    }
+}
+
+/// Calls `update()` on a popup when its content resizes, so it's laid out and placed again.
+///
+/// Observes until dropped.
+struct ContentResizeObserver {
+    observer: web_sys::ResizeObserver,
+    _callback: Closure<dyn Fn()>,
+}
+
+impl ContentResizeObserver {
+    fn new(content: &web_sys::Element, popup: &leaflet::Popup) -> Option<Self> {
+        let popup = popup.clone();
+        // Does nothing while the popup is closed
+        let callback = Closure::<dyn Fn()>::new(move || {
+            call_method(&popup, "update", &[]);
+        });
+        let observer = web_sys::ResizeObserver::new(callback.as_ref().unchecked_ref()).ok()?;
+        observer.observe(content);
+        Some(Self {
+            observer,
+            _callback: callback,
+        })
+    }
+}
+
+impl Drop for ContentResizeObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
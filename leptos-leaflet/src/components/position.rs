@@ -1,4 +1,5 @@
-use leaflet::LatLng;
+use leaflet::{to_lat_lng_array, LatLng};
+use wasm_bindgen::JsValue;
 
 use crate::core::IntoLatLng;
 
@@ -20,6 +21,23 @@ impl Position {
         Self { lat, lng }
     }
 
+    /// Creates a new position from planar coordinates, as used by `Crs::Simple`.
+    ///
+    /// Leaflet stores `y` as the latitude and `x` as the longitude.
+    pub fn from_xy(x: f64, y: f64) -> Self {
+        Self { lat: y, lng: x }
+    }
+
+    /// Planar `x` coordinate of the position, same as `lng`.
+    pub fn x(&self) -> f64 {
+        self.lng
+    }
+
+    /// Planar `y` coordinate of the position, same as `lat`.
+    pub fn y(&self) -> f64 {
+        self.lat
+    }
+
     /// Determines the distance between two positions using the Haversine formula.
     ///
     /// The result is in meters
@@ -72,6 +90,16 @@ impl Position {
         inside
     }
 
+    /// Check if the position is inside a polygon, and outside of all its holes
+    pub fn inside_polygon_with_holes(&self, polygon: &[Position], holes: &[Vec<Position>]) -> bool {
+        self.inside_polygon(polygon) && !holes.iter().any(|hole| self.inside_polygon(hole))
+    }
+
+    /// Check if the position is inside any of the polygons of a multi-polygon
+    pub fn inside_multi_polygon(&self, polygons: &[PolygonRings]) -> bool {
+        polygons.iter().any(|polygon| polygon.contains(self))
+    }
+
     /// Distance between two positions using pytagore theorem
     pub fn distance(&self, other: &Self) -> f64 {
         ((self.lat - other.lat).powi(2) + (self.lng - other.lng).powi(2)).sqrt()
@@ -85,8 +113,19 @@ impl Position {
     pub fn as_lat_lng(&self) -> LatLng {
         LatLng::new(self.lat, self.lng)
     }
+
+    /// Checks if two positions are equal within a small tolerance.
+    ///
+    /// Used to tell apart programmatic updates from values read back from the map.
+    pub(crate) fn approx_eq(&self, other: &Self) -> bool {
+        (self.lat - other.lat).abs() <= POSITION_EPSILON
+            && (self.lng - other.lng).abs() <= POSITION_EPSILON
+    }
 }
 
+/// Tolerance used when comparing positions read back from Leaflet.
+pub(crate) const POSITION_EPSILON: f64 = 1e-9;
+
 /// Winding number of a polygon
 #[allow(unused)]
 fn winding_number(poly: &[Position], point: &Position) -> i32 {
@@ -111,6 +150,58 @@ fn is_left(p0: &Position, p1: &Position, p2: &Position) -> f64 {
     (p1.lat - p0.lat) * (p2.lng - p0.lng) - (p2.lat - p0.lat) * (p1.lng - p0.lng)
 }
 
+/// The rings of a polygon: the outer ring, and the holes cut out of it.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PolygonRings {
+    pub outer: Vec<Position>,
+    pub holes: Vec<Vec<Position>>,
+}
+
+impl PolygonRings {
+    /// Creates a polygon without holes
+    pub fn new(outer: Vec<Position>) -> Self {
+        Self {
+            outer,
+            holes: Vec::new(),
+        }
+    }
+
+    /// Creates a polygon with holes
+    pub fn with_holes(outer: Vec<Position>, holes: Vec<Vec<Position>>) -> Self {
+        Self { outer, holes }
+    }
+
+    /// Check if the position is inside the polygon, and outside of all its holes
+    pub fn contains(&self, position: &Position) -> bool {
+        position.inside_polygon_with_holes(&self.outer, &self.holes)
+    }
+
+    /// Nested arrays of `LatLng`, as expected by Leaflet for polygons with holes.
+    pub fn to_lat_lngs(&self) -> js_sys::Array {
+        std::iter::once(&self.outer)
+            .chain(self.holes.iter())
+            .map(|ring| JsValue::from(to_lat_lng_array(ring)))
+            .collect()
+    }
+}
+
+impl From<Vec<Position>> for PolygonRings {
+    fn from(value: Vec<Position>) -> Self {
+        Self::new(value)
+    }
+}
+
+/// The first ring is the outer one, the others are the holes.
+impl From<Vec<Vec<Position>>> for PolygonRings {
+    fn from(mut value: Vec<Vec<Position>>) -> Self {
+        if value.is_empty() {
+            return Self::default();
+        }
+        let outer = value.remove(0);
+        Self::with_holes(outer, value)
+    }
+}
+
 impl From<Position> for LatLng {
     fn from(value: Position) -> Self {
         LatLng::new(value.lat, value.lng)
@@ -123,6 +214,18 @@ impl From<&Position> for LatLng {
     }
 }
 
+impl From<LatLng> for Position {
+    fn from(value: LatLng) -> Self {
+        Self::new(value.lat(), value.lng())
+    }
+}
+
+impl From<&LatLng> for Position {
+    fn from(value: &LatLng) -> Self {
+        Self::new(value.lat(), value.lng())
+    }
+}
+
 impl From<Position> for (f64, f64) {
     fn from(value: Position) -> Self {
         (value.lat, value.lng)
@@ -169,3 +272,46 @@ pub fn positions(positions: &[(f64, f64)]) -> Vec<Position> {
         .map(|&(lat, lng)| Position::new(lat, lng))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(min: f64, max: f64) -> Vec<Position> {
+        positions(&[(min, min), (min, max), (max, max), (max, min)])
+    }
+
+    #[test]
+    fn test_inside_polygon_with_holes() {
+        let outer = square(0.0, 10.0);
+        let holes = vec![square(4.0, 6.0)];
+        assert!(Position::new(2.0, 2.0).inside_polygon_with_holes(&outer, &holes));
+        assert!(!Position::new(5.0, 5.0).inside_polygon_with_holes(&outer, &holes));
+        assert!(!Position::new(12.0, 5.0).inside_polygon_with_holes(&outer, &holes));
+        // Without holes it behaves like `inside_polygon`
+        assert!(Position::new(5.0, 5.0).inside_polygon_with_holes(&outer, &[]));
+    }
+
+    #[test]
+    fn test_inside_multi_polygon() {
+        let polygons = vec![
+            PolygonRings::with_holes(square(0.0, 10.0), vec![square(4.0, 6.0)]),
+            PolygonRings::new(square(20.0, 30.0)),
+        ];
+        assert!(Position::new(2.0, 2.0).inside_multi_polygon(&polygons));
+        assert!(Position::new(25.0, 25.0).inside_multi_polygon(&polygons));
+        assert!(!Position::new(5.0, 5.0).inside_multi_polygon(&polygons));
+        assert!(!Position::new(15.0, 15.0).inside_multi_polygon(&polygons));
+    }
+
+    #[test]
+    fn test_polygon_rings_from_nested_vec() {
+        let rings = PolygonRings::from(vec![square(0.0, 10.0), square(4.0, 6.0)]);
+        assert_eq!(rings.outer, square(0.0, 10.0));
+        assert_eq!(rings.holes, vec![square(4.0, 6.0)]);
+        assert_eq!(
+            PolygonRings::from(Vec::<Vec<Position>>::new()),
+            PolygonRings::default()
+        );
+    }
+}
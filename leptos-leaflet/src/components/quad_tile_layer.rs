@@ -4,7 +4,7 @@ use wasm_bindgen::prelude::*;
 
 use crate::core::JsStoredValue;
 
-use super::LeafletMapContext;
+use super::use_layer_parent;
 
 /// Converts tile coordinates (x, y, z) to a quadkey string.
 /// Based on Microsoft's QuadKey algorithm.
@@ -73,14 +73,14 @@ pub fn QuadTileLayer(
     #[prop(default = 0.0)] min_zoom: f64,
     #[prop(default = 18.0)] max_zoom: f64,
 ) -> impl IntoView {
-    let map_context = use_context::<LeafletMapContext>().expect("map context not found");
+    let layer_parent = use_layer_parent();
     
     // Store the closure to prevent memory leaks
     let get_tile_url_closure: JsStoredValue<Option<Closure<dyn Fn(JsValue) -> String>>> = 
         JsStoredValue::new_local(None);
 
     Effect::new(move |_| {
-        if let Some(map) = map_context.map() {
+        if layer_parent.is_ready() {
             // Create tile layer options
             let options = leaflet::TileLayerOptions::default();
             if !attribution.is_empty() {
@@ -126,7 +126,7 @@ pub fn QuadTileLayer(
             // Store the closure to prevent it from being dropped
             get_tile_url_closure.set_value(Some(closure));
             
-            map_layer.add_to(&map);
+            layer_parent.add_layer(&map_layer);
 
             match (bring_to_front, bring_to_back) {
                 (true, true) => warn!("The parameters are set to bring the layer to front and back at the same time. Ignoring these parameters..."),
@@ -138,7 +138,7 @@ pub fn QuadTileLayer(
             let map_layer = JsStoredValue::new_local(map_layer);
 
             on_cleanup(move || {
-                map_layer.with_value(|v| v.remove());
+                map_layer.with_value(|v| layer_parent.remove_layer(v));
             });
         }
     });
@@ -0,0 +1,447 @@
+use leptos::prelude::*;
+
+use super::draw_control::map_distance;
+use super::{use_layer_parent, use_leaflet_context, Bounds, LayerParent, Position};
+use crate::core::{JsSignal, JsStoredValue, LeafletEventListener};
+
+/// Size in pixels of the edit handles.
+const HANDLE_SIZE: f64 = 10.0;
+
+/// Shows handles to drag, insert and delete the vertices of a line or polygon.
+///
+/// Vertices are dragged, dragging the handle in the middle of a segment inserts a vertex, and
+/// clicking a vertex deletes it while more than `min_vertices` are left. Every change is written
+/// to `positions`.
+pub(crate) fn edit_vertices(
+    positions: RwSignal<Vec<Position>>,
+    closed: bool,
+    min_vertices: usize,
+    editing: Signal<bool>,
+) {
+    let layer_parent = use_layer_parent();
+    let handles = JsStoredValue::new_local(EditHandles::new(layer_parent));
+    let dragged = StoredValue::new(None::<usize>);
+    let drag_end = Trigger::new();
+
+    Effect::new(move |_| {
+        drag_end.track();
+        let current = positions.get();
+        if !layer_parent.is_ready() {
+            return;
+        }
+        if !editing.get() {
+            handles.update_value(EditHandles::clear);
+            return;
+        }
+        let mut handle_positions = current.clone();
+        handle_positions.extend(midpoints(&current, closed));
+        // The number of handles only changes once an inserted vertex is dropped
+        if handles.with_value(|handles| handles.move_to(&handle_positions, dragged.get_value()))
+            || dragged.get_value().is_some()
+        {
+            return;
+        }
+
+        handles.update_value(|handles| {
+            handles.clear();
+            for (index, position) in current.iter().enumerate() {
+                let marker = handles.add(*position, "leaflet-edit-vertex");
+                let moved = marker.clone();
+                handles.on(&marker, "dragstart", move || dragged.set_value(Some(index)));
+                handles.on(&marker, "drag", move || {
+                    let position = Position::from(moved.get_lat_lng());
+                    positions.update(|positions| {
+                        if let Some(vertex) = positions.get_mut(index) {
+                            *vertex = position;
+                        }
+                    });
+                });
+                handles.on(&marker, "dragend", move || {
+                    dragged.set_value(None);
+                    drag_end.notify();
+                });
+                handles.on(&marker, "click", move || {
+                    positions.update(|positions| {
+                        remove_vertex(positions, index, min_vertices);
+                    });
+                });
+            }
+            for (index, position) in midpoints(&current, closed).into_iter().enumerate() {
+                let marker = handles.add(position, "leaflet-edit-midpoint");
+                let moved = marker.clone();
+                let inserted = index + 1;
+                let handle = current.len() + index;
+                handles.on(&marker, "dragstart", move || {
+                    dragged.set_value(Some(handle));
+                    positions.update(|positions| {
+                        positions.insert(inserted.min(positions.len()), position);
+                    });
+                });
+                handles.on(&marker, "drag", move || {
+                    let position = Position::from(moved.get_lat_lng());
+                    positions.update(|positions| {
+                        if let Some(vertex) = positions.get_mut(inserted) {
+                            *vertex = position;
+                        }
+                    });
+                });
+                handles.on(&marker, "dragend", move || {
+                    dragged.set_value(None);
+                    drag_end.notify();
+                });
+            }
+        });
+    });
+
+    on_cleanup(move || {
+        handles.try_update_value(EditHandles::clear);
+    });
+}
+
+/// Shows handles on the corners of a rectangle, dragging one resizes it from the opposite corner.
+pub(crate) fn edit_bounds(bounds: RwSignal<Bounds>, editing: Signal<bool>) {
+    let layer_parent = use_layer_parent();
+    let handles = JsStoredValue::new_local(EditHandles::new(layer_parent));
+    // The dragged handle, with the corner it is on once dragged past the anchor
+    let dragged = StoredValue::new(None::<(usize, usize)>);
+    let anchor = StoredValue::new(None::<Position>);
+    let drag_end = Trigger::new();
+
+    Effect::new(move |_| {
+        drag_end.track();
+        let corner_positions = corners(&bounds.get());
+        if !layer_parent.is_ready() {
+            return;
+        }
+        if !editing.get() {
+            handles.update_value(EditHandles::clear);
+            return;
+        }
+        let dragged_handle = dragged.get_value();
+        let handle_positions = corner_handle_positions(corner_positions, dragged_handle);
+        if handles.with_value(|handles| {
+            handles.move_to(&handle_positions, dragged_handle.map(|(handle, _)| handle))
+        }) {
+            return;
+        }
+
+        handles.update_value(|handles| {
+            handles.clear();
+            for (index, corner) in corner_positions.iter().enumerate() {
+                let marker = handles.add(*corner, "leaflet-edit-vertex");
+                let moved = marker.clone();
+                handles.on(&marker, "dragstart", move || {
+                    dragged.set_value(Some((index, index)));
+                    anchor.set_value(Some(corners(&bounds.get_untracked())[(index + 2) % 4]));
+                });
+                handles.on(&marker, "drag", move || {
+                    if let Some(anchor) = anchor.get_value() {
+                        let corner = Position::from(moved.get_lat_lng());
+                        let resized = Bounds::from_corners(anchor, corner);
+                        dragged.set_value(Some((index, closest_corner(&resized, corner))));
+                        bounds.set(resized);
+                    }
+                });
+                handles.on(&marker, "dragend", move || {
+                    dragged.set_value(None);
+                    anchor.set_value(None);
+                    drag_end.notify();
+                });
+            }
+        });
+    });
+
+    on_cleanup(move || {
+        handles.try_update_value(EditHandles::clear);
+    });
+}
+
+/// Shows a handle to drag the center of a circle, and one on its edge to change the radius.
+///
+/// Each handle is only shown when the matching signal is set.
+pub(crate) fn edit_circle(
+    center: JsSignal<Position>,
+    radius: Signal<f64>,
+    editable_center: Option<RwSignal<Position>>,
+    editable_radius: Option<RwSignal<f64>>,
+    editing: Signal<bool>,
+) {
+    let map_context = use_leaflet_context().expect("map context");
+    let layer_parent = use_layer_parent();
+    let handles = JsStoredValue::new_local(EditHandles::new(layer_parent));
+    let dragged = StoredValue::new(None::<usize>);
+    let drag_end = Trigger::new();
+
+    Effect::new(move |_| {
+        drag_end.track();
+        let (center_position, radius_value) = (center.get(), radius.get());
+        if !layer_parent.is_ready() {
+            return;
+        }
+        let Some(map) = map_context.map_untracked() else {
+            return;
+        };
+        if !editing.get() {
+            handles.update_value(EditHandles::clear);
+            return;
+        }
+        let per_degree = map_distance(
+            &map,
+            &center_position,
+            &Position::new(center_position.lat, center_position.lng + 1.0),
+        );
+        let edge = radius_handle_position(center_position, radius_value, per_degree);
+        let handle_positions = editable_center
+            .map(|_| center_position)
+            .into_iter()
+            .chain(editable_radius.map(|_| edge))
+            .collect::<Vec<_>>();
+        if handles.with_value(|handles| handles.move_to(&handle_positions, dragged.get_value())) {
+            return;
+        }
+
+        handles.update_value(|handles| {
+            handles.clear();
+            let mut index = 0;
+            if let Some(editable_center) = editable_center {
+                let marker = handles.add(center_position, "leaflet-edit-center");
+                let moved = marker.clone();
+                let handle = index;
+                handles.on(&marker, "dragstart", move || {
+                    dragged.set_value(Some(handle))
+                });
+                handles.on(&marker, "drag", move || {
+                    editable_center.set(Position::from(moved.get_lat_lng()));
+                });
+                handles.on(&marker, "dragend", move || {
+                    dragged.set_value(None);
+                    drag_end.notify();
+                });
+                index += 1;
+            }
+            if let Some(editable_radius) = editable_radius {
+                let marker = handles.add(edge, "leaflet-edit-radius");
+                let moved = marker.clone();
+                let handle = index;
+                let distance_map = map.clone();
+                handles.on(&marker, "dragstart", move || {
+                    dragged.set_value(Some(handle))
+                });
+                handles.on(&marker, "drag", move || {
+                    let edge = Position::from(moved.get_lat_lng());
+                    let center = center.get_untracked();
+                    editable_radius.set(map_distance(&distance_map, &center, &edge));
+                });
+                handles.on(&marker, "dragend", move || {
+                    dragged.set_value(None);
+                    drag_end.notify();
+                });
+            }
+        });
+    });
+
+    on_cleanup(move || {
+        handles.try_update_value(EditHandles::clear);
+    });
+}
+
+/// Draggable markers used as edit handles, with their listeners.
+///
+/// The handles are added to the parent of the shape, so they are hidden with it when the parent
+/// group is not on the map.
+struct EditHandles {
+    parent: LayerParent,
+    markers: Vec<leaflet::Marker>,
+    listeners: Vec<LeafletEventListener>,
+}
+
+impl EditHandles {
+    fn new(parent: LayerParent) -> Self {
+        Self {
+            parent,
+            markers: Vec::new(),
+            listeners: Vec::new(),
+        }
+    }
+
+    /// Adds a handle, styled with the `leaflet-edit-handle` class and `class_name`.
+    fn add(&mut self, position: Position, class_name: &str) -> leaflet::Marker {
+        let icon_options = leaflet::DivIconOptions::new();
+        icon_options.set_class_name(format!("leaflet-div-icon leaflet-edit-handle {class_name}"));
+        icon_options.set_icon_size(leaflet::Point::new(HANDLE_SIZE, HANDLE_SIZE));
+        let options = leaflet::MarkerOptions::new();
+        options.set_draggable(true);
+        options.set_icon(leaflet::DivIcon::new(&icon_options).into());
+        let marker = leaflet::Marker::new_with_options(&position.as_lat_lng(), &options);
+        self.parent.add_layer(&marker);
+        self.markers.push(marker.clone());
+        marker
+    }
+
+    fn on(&mut self, marker: &leaflet::Marker, kind: &str, handler: impl Fn() + 'static) {
+        self.listeners.push(LeafletEventListener::new(
+            marker,
+            kind,
+            move |_: leaflet::Event| handler(),
+        ));
+    }
+
+    /// Moves the handles to `positions`, except the `dragged` one.
+    ///
+    /// Returns false when the number of handles doesn't match, to create them again.
+    fn move_to(&self, positions: &[Position], dragged: Option<usize>) -> bool {
+        if self.markers.is_empty() || self.markers.len() != positions.len() {
+            return false;
+        }
+        for (index, (marker, position)) in self.markers.iter().zip(positions).enumerate() {
+            if dragged != Some(index) {
+                marker.set_lat_lng(&position.as_lat_lng());
+            }
+        }
+        true
+    }
+
+    fn clear(&mut self) {
+        self.listeners.clear();
+        for marker in self.markers.drain(..) {
+            self.parent.remove_layer(&marker);
+        }
+    }
+}
+
+/// The middle of every segment, including the closing one of a polygon.
+fn midpoints(positions: &[Position], closed: bool) -> Vec<Position> {
+    let segments = match positions.len() {
+        0 | 1 => 0,
+        2 => 1,
+        len if closed => len,
+        len => len - 1,
+    };
+    (0..segments)
+        .map(|index| {
+            let (a, b) = (positions[index], positions[(index + 1) % positions.len()]);
+            Position::new((a.lat + b.lat) / 2.0, (a.lng + b.lng) / 2.0)
+        })
+        .collect()
+}
+
+/// Removes the vertex at `index` if more than `min_vertices` are left, returning true if it did.
+fn remove_vertex(positions: &mut Vec<Position>, index: usize, min_vertices: usize) -> bool {
+    if positions.len() <= min_vertices || index >= positions.len() {
+        return false;
+    }
+    positions.remove(index);
+    true
+}
+
+/// The corners of the bounds, in order around the rectangle.
+fn corners(bounds: &Bounds) -> [Position; 4] {
+    [
+        bounds.get_top_left(),
+        bounds.get_top_right(),
+        bounds.get_bottom_right(),
+        bounds.get_bottom_left(),
+    ]
+}
+
+/// The index in [`corners`] of the corner of the bounds closest to `position`.
+fn closest_corner(bounds: &Bounds, position: Position) -> usize {
+    corners(bounds)
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.distance(&position).total_cmp(&b.distance(&position)))
+        .map_or(0, |(index, _)| index)
+}
+
+/// The positions of the corner handles, in the order of the handles.
+///
+/// Dragging a handle past the anchor moves it to another corner, it then swaps places with the
+/// handle of that corner so every corner keeps one handle.
+///
+/// # Arguments
+///
+/// * `dragged` - The dragged handle and the corner it is on.
+fn corner_handle_positions(
+    mut corners: [Position; 4],
+    dragged: Option<(usize, usize)>,
+) -> [Position; 4] {
+    if let Some((handle, corner)) = dragged {
+        corners.swap(handle, corner);
+    }
+    corners
+}
+
+/// The point east of the center on the edge of the circle.
+///
+/// # Arguments
+///
+/// * `per_degree` - The distance of one degree of longitude at the center, in the radius unit.
+fn radius_handle_position(center: Position, radius: f64, per_degree: f64) -> Position {
+    if per_degree <= 0.0 {
+        return center;
+    }
+    Position::new(center.lat, center.lng + radius / per_degree)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_midpoints() {
+        let square = [
+            Position::new(0.0, 0.0),
+            Position::new(0.0, 2.0),
+            Position::new(2.0, 2.0),
+        ];
+        assert_eq!(
+            midpoints(&square, false),
+            vec![Position::new(0.0, 1.0), Position::new(1.0, 2.0)]
+        );
+        assert_eq!(midpoints(&square, true)[2], Position::new(1.0, 1.0));
+        assert_eq!(midpoints(&square[..2], true).len(), 1);
+        assert!(midpoints(&square[..1], true).is_empty());
+    }
+
+    #[test]
+    fn test_remove_vertex_keeps_min_vertices() {
+        let mut positions = vec![
+            Position::new(0.0, 0.0),
+            Position::new(0.0, 1.0),
+            Position::new(1.0, 1.0),
+        ];
+        assert!(!remove_vertex(&mut positions, 0, 3));
+        assert!(!remove_vertex(&mut positions, 5, 2));
+        assert!(remove_vertex(&mut positions, 1, 2));
+        assert_eq!(
+            positions,
+            vec![Position::new(0.0, 0.0), Position::new(1.0, 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_corner_handles_follow_the_dragged_corner() {
+        let anchor = Position::new(0.0, 2.0);
+        // The top left handle (0) dragged past the right of the anchor is on the top right corner
+        let cursor = Position::new(2.0, 3.0);
+        let resized = Bounds::from_corners(anchor, cursor);
+        assert_eq!(closest_corner(&resized, cursor), 1);
+
+        let positions = corner_handle_positions(corners(&resized), Some((0, 1)));
+        assert_eq!(positions[0], cursor);
+        // The handle that was on the top right corner takes the free top left corner
+        assert_eq!(positions[1], Position::new(2.0, 2.0));
+        assert_eq!(positions[2], Position::new(0.0, 3.0));
+        assert_eq!(positions[3], anchor);
+    }
+
+    #[test]
+    fn test_radius_handle_position() {
+        let center = Position::new(10.0, 20.0);
+        assert_eq!(
+            radius_handle_position(center, 500.0, 100.0),
+            Position::new(10.0, 25.0)
+        );
+        assert_eq!(radius_handle_position(center, 500.0, 0.0), center);
+    }
+}
@@ -0,0 +1,173 @@
+use leptos::logging::error;
+use leptos::prelude::*;
+use leptos::svg::Svg;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use super::{use_layer_parent, use_pane_context, Bounds};
+use crate::core::{get_function, leaflet_namespace, JsStoredValue};
+
+/// An SVG overlay component, that stretches its SVG children over the given bounds.
+///
+/// The children are regular Leptos views and stay reactive on the map. Use `view_box` to draw in
+/// your own coordinates instead of pixels.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use leptos::prelude::*;
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn Gauge(level: Signal<f64>) -> impl IntoView {
+///     view! {
+///         <SvgOverlay
+///             bounds=Bounds::new(position!(51.51, -0.08), position!(51.5, -0.1))
+///             view_box="0 0 100 100"
+///         >
+///             <rect x="0" y="0" width="100" height="100" fill="none" stroke="black" />
+///             <rect x="0" y=move || 100.0 - level.get() width="100" height=move || level.get() fill="blue" />
+///         </SvgOverlay>
+///     }
+/// }
+/// ```
+#[component(transparent)]
+pub fn SvgOverlay(
+    #[prop(into)] bounds: Signal<Bounds>,
+    /// The `viewBox` attribute of the SVG element.
+    #[prop(into, optional)]
+    view_box: Option<Signal<String>>,
+    #[prop(into, optional)] opacity: Option<Signal<f64>>,
+    #[prop(into, optional)] alt: Option<Signal<String>>,
+    #[prop(into, optional)] interactive: Option<Signal<bool>>,
+    #[prop(into, optional)] z_index: Option<Signal<f64>>,
+    #[prop(into, optional)] class_name: Option<Signal<String>>,
+    #[prop(into, optional)] bubbling_mouse_events: Option<Signal<bool>>,
+    #[prop(into, optional)] pane: Option<Signal<String>>,
+    #[prop(into, optional)] attribution: Option<Signal<String>>,
+    children: Children,
+) -> impl IntoView {
+    let layer_parent = use_layer_parent();
+    let overlay = JsStoredValue::new_local(None::<leaflet::ImageOverlay>);
+
+    // Render the SVG content to an element, which Leaflet moves into the overlay pane
+    let content = NodeRef::<Svg>::new();
+
+    Effect::new(move |_| {
+        if layer_parent.is_ready() {
+            let Some(content) = content.get_untracked() else {
+                return;
+            };
+            let options = leaflet::ImageOverlayOptions::new();
+            if let Some(opacity) = opacity {
+                options.set_opacity(opacity.get_untracked());
+            }
+            if let Some(alt) = &alt {
+                options.set_alt(alt.get_untracked());
+            }
+            if let Some(interactive) = interactive {
+                options.set_interactive(interactive.get_untracked());
+            }
+            if let Some(z_index) = z_index {
+                options.set_z_index(z_index.get_untracked());
+            }
+            if let Some(class_name) = &class_name {
+                options.set_class_name(class_name.get_untracked());
+            }
+            if let Some(bubbling_mouse_events) = &bubbling_mouse_events {
+                options.set_bubbling_mouse_events(bubbling_mouse_events.get_untracked());
+            }
+
+            // Use explicit pane if provided, otherwise use pane context if available
+            if let Some(pane) = &pane {
+                let pane_value = pane.get_untracked();
+                if !pane_value.is_empty() {
+                    options.set_pane(pane_value);
+                }
+            } else if let Some(pane_context) = use_pane_context() {
+                options.set_pane(pane_context.name().to_string());
+            }
+            if let Some(attribution) = &attribution {
+                options.set_attribution(attribution.get_untracked());
+            }
+
+            let Some(svg_overlay) = create_svg_overlay(
+                content.unchecked_ref(),
+                &bounds.get_untracked().as_lat_lng_bounds(),
+                &options,
+            ) else {
+                return;
+            };
+            layer_parent.add_layer(&svg_overlay);
+            overlay.set_value(Some(svg_overlay));
+        }
+    });
+
+    let bounds_stop = Effect::watch(
+        move || bounds.get(),
+        move |bounds, _, _| {
+            if let Some(overlay) = overlay.get_value() {
+                overlay.set_bounds(&bounds.as_lat_lng_bounds());
+            }
+        },
+        false,
+    );
+
+    let opacity_stop = Effect::watch(
+        move || opacity.map(|opacity| opacity.get()),
+        move |opacity, _, _| {
+            if let (Some(opacity), Some(overlay)) = (opacity, overlay.get_value()) {
+                overlay.set_opacity(*opacity);
+            }
+        },
+        false,
+    );
+
+    let z_index_stop = Effect::watch(
+        move || z_index.map(|z_index| z_index.get()),
+        move |z_index, _, _| {
+            if let (Some(z_index), Some(overlay)) = (z_index, overlay.get_value()) {
+                overlay.set_z_index(*z_index);
+            }
+        },
+        false,
+    );
+
+    on_cleanup(move || {
+        bounds_stop.stop();
+        opacity_stop.stop();
+        z_index_stop.stop();
+        if let Some(overlay) = overlay.try_get_value().flatten() {
+            layer_parent.remove_layer(&overlay);
+        }
+    });
+
+    view! {
+        <div style="visibility:collapse">
+            <svg
+                node_ref=content
+                xmlns="http://www.w3.org/2000/svg"
+                viewBox=move || view_box.map(|view_box| view_box.get())
+            >
+                {children()}
+            </svg>
+        </div>
+    }
+}
+
+/// Creates the overlay with `L.svgOverlay`, which extends `L.ImageOverlay`.
+fn create_svg_overlay(
+    element: &JsValue,
+    bounds: &leaflet::LatLngBounds,
+    options: &leaflet::ImageOverlayOptions,
+) -> Option<leaflet::ImageOverlay> {
+    let leaflet = leaflet_namespace()?;
+    let svg_overlay = get_function(&leaflet, "svgOverlay")?;
+    match svg_overlay.call3(&leaflet, element, bounds, options) {
+        Ok(overlay) => Some(overlay.unchecked_into()),
+        Err(err) => {
+            error!("Failed to create SVG overlay: {:?}", err);
+            None
+        }
+    }
+}
@@ -3,7 +3,7 @@ use leptos::prelude::*;
 
 use crate::core::JsStoredValue;
 
-use super::LeafletMapContext;
+use super::use_layer_parent;
 
 /// A tile layer component.
 #[component(transparent)]
@@ -15,10 +15,10 @@ pub fn TileLayer(
     #[prop(default = 0.0)] min_zoom: f64,
     #[prop(default = 18.0)] max_zoom: f64,
 ) -> impl IntoView {
-    let map_context = use_context::<LeafletMapContext>().expect("map context not found");
+    let layer_parent = use_layer_parent();
 
     Effect::new(move |_| {
-        if let Some(map) = map_context.map() {
+        if layer_parent.is_ready() {
             let options = leaflet::TileLayerOptions::default();
             if !attribution.is_empty() {
                 options.set_attribution(attribution.to_string());
@@ -26,7 +26,7 @@ pub fn TileLayer(
             options.set_min_zoom(min_zoom);
             options.set_max_zoom(max_zoom);
             let map_layer = leaflet::TileLayer::new_options(&url, &options);
-            map_layer.add_to(&map);
+            layer_parent.add_layer(&map_layer);
 
             match (bring_to_front, bring_to_back) {
                 (true, true) => warn!("The parameters are set to bring the layer to front and back at the same time. Ignoring these parameters..."),
@@ -38,7 +38,7 @@ pub fn TileLayer(
             let map_layer = JsStoredValue::new_local(map_layer);
 
             on_cleanup(move || {
-                map_layer.with_value(|v| v.remove());
+                map_layer.with_value(|v| layer_parent.remove_layer(v));
             });
         }
     });
@@ -3,7 +3,7 @@ use leptos::logging::warn;
 use leptos::prelude::*;
 use leaflet::{Map, TileLayerWms as LeafletTileLayerWms, TileLayerWmsOptions};
 
-use super::{LeafletMapContext, MapEvents, TileLayerWmsContext};
+use super::{use_layer_parent, LeafletMapContext, MapEvents, TileLayerWmsContext};
 use crate::core::IntoThreadSafeJsValue;
 
 /// A WMS tile layer component.
@@ -15,16 +15,16 @@ pub fn TileLayerWms(
     #[prop(optional)] bring_to_front: bool,
     #[prop(optional)] bring_to_back: bool,
 ) -> impl IntoView {
-    let map_context = use_context::<LeafletMapContext>().expect("map context not found");
+    let layer_parent = use_layer_parent();
     let wms_context = TileLayerWmsContext::new();
     provide_context(wms_context);
 
     Effect::new(move |_| {
-        if let Some(map) = map_context.map() {
+        if layer_parent.is_ready() {
             let options = options.get_value();
             let map_layer =
                 leaflet::TileLayerWms::new_options(&url, &options).into_thread_safe_js_value();
-            map_layer.add_to(&map);
+            layer_parent.add_layer(map_layer.value());
             wms_context.set_wms(&map_layer);
 
             match (bring_to_front, bring_to_back) {
@@ -35,7 +35,7 @@ pub fn TileLayerWms(
             }
 
             on_cleanup(move || {
-                map_layer.remove();
+                layer_parent.remove_layer(map_layer.value());
             });
         }
     });
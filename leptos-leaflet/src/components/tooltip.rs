@@ -2,8 +2,9 @@ use leptos::html::Div;
 use leptos::prelude::*;
 use wasm_bindgen::prelude::*;
 
-use crate::core::{IntoThreadSafeJsValue, JsSignal};
+use crate::core::{IntoThreadSafeJsValue, JsSignal, JsStoredValue, LeafletEventListener};
 
+use super::open_state::{OverlayKind, OverlaySource};
 use super::{
     use_pane_context, LeafletMapContext, LeafletOverlayContainerContext, PaneStrategy, Position,
 };
@@ -116,6 +117,7 @@ use super::{
 /// # Props
 ///
 /// - `pane_strategy`: Controls how the tooltip determines which pane to use
+/// - `open`: Opens or closes the tooltip from app state, and follows it opening on hover
 /// - Other props control tooltip behavior like position, permanence, direction, etc.
 #[component]
 pub fn Tooltip(
@@ -125,10 +127,15 @@ pub fn Tooltip(
     #[prop(into, optional, default="auto".into())] direction: Signal<String>,
     #[prop(into, optional)] sticky: Signal<bool>,
     #[prop(into, optional, default=0.9.into())] opacity: Signal<f64>,
+    /// Whether the tooltip is open, set when it opens or closes on hover.
+    #[prop(optional)]
+    open: Option<RwSignal<bool>>,
     children: Children,
 ) -> impl IntoView {
     let map_context = use_context::<LeafletMapContext>();
     let overlay_context = use_context::<LeafletOverlayContainerContext>();
+    let tooltip_source = JsStoredValue::new_local(None::<(leaflet::Tooltip, OverlaySource)>);
+    let open_listeners = JsStoredValue::new_local(Vec::<LeafletEventListener>::new());
 
     let content = NodeRef::<Div>::new();
     // let content = view! { <div>{children()}</div>};
@@ -165,7 +172,12 @@ pub fn Tooltip(
                 let content = content.get_untracked().expect("content ref");
                 tooltip.set_content(content.unchecked_ref());
                 layer.bind_tooltip(&tooltip);
+                let source = OverlaySource::Layer(layer);
+                source.bind_open(OverlayKind::Tooltip, tooltip.value(), open, open_listeners);
+                tooltip_source.set_value(Some((tooltip.value().clone(), source)));
                 on_cleanup(move || {
+                    open_listeners.try_update_value(Vec::clear);
+                    tooltip_source.try_set_value(None);
                     tooltip.remove();
                 });
             }
@@ -178,12 +190,33 @@ pub fn Tooltip(
             let content = content.get_untracked().expect("content ref");
             let html_view: &JsValue = content.unchecked_ref();
             tooltip.set_content(html_view);
-            tooltip.open_on(&map);
+            if open.is_none() {
+                tooltip.open_on(&map);
+            }
+            let source = OverlaySource::Map(map);
+            source.bind_open(OverlayKind::Tooltip, tooltip.value(), open, open_listeners);
+            tooltip_source.set_value(Some((tooltip.value().clone(), source)));
             on_cleanup(move || {
+                open_listeners.try_update_value(Vec::clear);
+                tooltip_source.try_set_value(None);
                 tooltip.remove();
             });
         }
     });
 
+    let open_stop = Effect::watch(
+        move || open.map(|open| open.get()),
+        move |open, _, _| {
+            if let (Some(open), Some((tooltip, source))) = (open, tooltip_source.get_value()) {
+                source.set_open(OverlayKind::Tooltip, &tooltip, *open);
+            }
+        },
+        false,
+    );
+
+    on_cleanup(move || {
+        open_stop.stop();
+    });
+
     view! { <div style="visibility:collapse"><div node_ref=content>{children()}</div></div> }
 }
@@ -1,15 +1,46 @@
+use js_sys::{Function, Reflect};
 use leptos::logging::log;
 use leptos::prelude::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlVideoElement;
 
-use crate::core::IntoThreadSafeJsValue;
+use crate::core::{DomEventListener, JsStoredValue, LeafletEventListener};
 
-use super::{use_pane_context, Bounds, LeafletMapContext};
+use super::{use_layer_parent, use_pane_context, Bounds, VideoError, VideoEvents};
 
 /// A video overlay component.
+///
+/// Changes of `url`, `bounds`, `opacity` and `z_index` update the overlay in place, for example
+/// to animate frames without re-adding the layer.
+///
+/// Pass a [`VideoPlayback`] to control the video once it's on the map:
+///
+/// ```rust,no_run
+/// use leptos::prelude::*;
+/// use leptos_leaflet::prelude::*;
+///
+/// #[component]
+/// fn TimeLapse(bounds: Bounds) -> impl IntoView {
+///     let playback = VideoPlayback::new();
+///     view! {
+///         <VideoOverlay url="https://www.mapbox.com/bites/00188/patricia_nasa.webm" bounds=bounds playback=playback />
+///         <button on:click=move |_| playback.playing.update(|playing| *playing = !*playing)>
+///             "Play/Pause"
+///         </button>
+///         <input
+///             type="range"
+///             max=move || playback.duration().get().unwrap_or_default()
+///             prop:value=move || playback.current_time.get()
+///             on:input=move |ev| playback.current_time.set(event_target_value(&ev).parse().unwrap_or_default())
+///         />
+///     }
+/// }
+/// ```
 #[component(transparent)]
 pub fn VideoOverlay(
-    #[prop(into)] url: String,
-    #[prop(into)] bounds: Bounds,
+    #[prop(into)] url: Signal<String>,
+    #[prop(into)] bounds: Signal<Bounds>,
     #[prop(into, optional)] opacity: Option<Signal<f64>>,
     #[prop(into, optional)] alt: Option<Signal<String>>,
     #[prop(into, optional)] interactive: Option<Signal<bool>>,
@@ -26,11 +57,21 @@ pub fn VideoOverlay(
     #[prop(into, optional)] keep_aspect_ratio: Option<Signal<bool>>,
     #[prop(into, optional)] muted: Option<Signal<bool>>,
     #[prop(into, optional)] plays_inline: Option<Signal<bool>>,
+    /// Playback state synced with the video element.
+    #[prop(optional)]
+    playback: Option<VideoPlayback>,
+    #[prop(into, optional)] video_events: VideoEvents,
 ) -> impl IntoView {
-    let map_context = use_context::<LeafletMapContext>().expect("map context not found");
+    let layer_parent = use_layer_parent();
+    let overlay = JsStoredValue::new_local(None::<leaflet::VideoOverlay>);
+    let video = JsStoredValue::new_local(None::<HtmlVideoElement>);
+    let listeners = JsStoredValue::new_local(Vec::<DomEventListener>::new());
+    let add_listener = JsStoredValue::new_local(None::<LeafletEventListener>);
+
     Effect::new(move |_| {
-        if let Some(map) = map_context.map() {
-            log!("Adding image layer: {}", url);
+        if layer_parent.is_ready() {
+            let url = url.get_untracked();
+            log!("Adding video layer: {}", url);
             let options = leaflet::VideoOverlayOptions::new();
             if let Some(opacity) = opacity {
                 options.set_opacity(opacity.get_untracked());
@@ -90,14 +131,266 @@ pub fn VideoOverlay(
 
             let map_layer = leaflet::VideoOverlay::new_with_options(
                 &url,
-                &bounds.as_lat_lng_bounds(),
+                &bounds.get_untracked().as_lat_lng_bounds(),
                 &options,
-            )
-            .into_thread_safe_js_value();
-            map_layer.add_to(&map);
-            on_cleanup(move || {
-                map_layer.remove();
-            });
+            );
+
+            // Leaflet creates the video element when the layer is first added to a map
+            let video_layer = map_layer.clone();
+            let video_events = video_events.clone();
+            add_listener.set_value(Some(LeafletEventListener::new(
+                &map_layer,
+                "add",
+                move |_: leaflet::Event| {
+                    if video.with_value(Option::is_none) {
+                        if let Some(element) = video_element(&video_layer) {
+                            listeners.set_value(attach_video(&element, playback, &video_events));
+                            video.set_value(Some(element));
+                        }
+                    }
+                },
+            )));
+
+            layer_parent.add_layer(&map_layer);
+            overlay.set_value(Some(map_layer));
+        }
+    });
+
+    let url_stop = Effect::watch(
+        move || url.get(),
+        move |url, _, _| {
+            if let Some(overlay) = overlay.get_value() {
+                overlay.set_url(url);
+            }
+        },
+        false,
+    );
+
+    let bounds_stop = Effect::watch(
+        move || bounds.get(),
+        move |bounds, _, _| {
+            if let Some(overlay) = overlay.get_value() {
+                overlay.set_bounds(&bounds.as_lat_lng_bounds());
+            }
+        },
+        false,
+    );
+
+    let opacity_stop = Effect::watch(
+        move || opacity.map(|opacity| opacity.get()),
+        move |opacity, _, _| {
+            if let (Some(opacity), Some(overlay)) = (opacity, overlay.get_value()) {
+                overlay.set_opacity(*opacity);
+            }
+        },
+        false,
+    );
+
+    let z_index_stop = Effect::watch(
+        move || z_index.map(|z_index| z_index.get()),
+        move |z_index, _, _| {
+            if let (Some(z_index), Some(overlay)) = (z_index, overlay.get_value()) {
+                overlay.set_z_index(*z_index);
+            }
+        },
+        false,
+    );
+
+    let playing_stop = Effect::watch(
+        move || playback.map(|playback| playback.playing.get()),
+        move |_, _, _| {
+            if let (Some(playback), Some(video)) = (playback, video.get_value()) {
+                playback.apply_playing(&video);
+            }
+        },
+        false,
+    );
+
+    let current_time_stop = Effect::watch(
+        move || playback.map(|playback| playback.current_time.get()),
+        move |_, _, _| {
+            if let (Some(playback), Some(video)) = (playback, video.get_value()) {
+                playback.apply_current_time(&video);
+            }
+        },
+        false,
+    );
+
+    let playback_rate_stop = Effect::watch(
+        move || playback.map(|playback| playback.playback_rate.get()),
+        move |_, _, _| {
+            if let (Some(playback), Some(video)) = (playback, video.get_value()) {
+                playback.apply_playback_rate(&video);
+            }
+        },
+        false,
+    );
+
+    on_cleanup(move || {
+        url_stop.stop();
+        bounds_stop.stop();
+        opacity_stop.stop();
+        z_index_stop.stop();
+        playing_stop.stop();
+        current_time_stop.stop();
+        playback_rate_stop.stop();
+        listeners.try_update_value(Vec::clear);
+        add_listener.try_set_value(None);
+        if let Some(overlay) = overlay.try_get_value().flatten() {
+            layer_parent.remove_layer(&overlay);
         }
     });
 }
+
+/// Playback state of a [`VideoOverlay`], two-way bound to its video element.
+///
+/// Setting `playing`, `current_time` or `playback_rate` controls the video, and they are updated
+/// when the video plays, pauses, advances or ends.
+#[derive(Debug, Clone, Copy)]
+pub struct VideoPlayback {
+    /// Whether the video is playing. Set to `false` when the browser blocks autoplay.
+    pub playing: RwSignal<bool>,
+    /// Position in the video in seconds, setting it seeks.
+    pub current_time: RwSignal<f64>,
+    /// Speed of the playback, `1.0` being the normal speed.
+    pub playback_rate: RwSignal<f64>,
+    duration: RwSignal<Option<f64>>,
+    /// Last time read from or written to the video, to tell seeks from updates.
+    synced_time: StoredValue<f64>,
+}
+
+impl VideoPlayback {
+    /// Creates a paused playback at the start of the video.
+    pub fn new() -> Self {
+        Self {
+            playing: RwSignal::new(false),
+            current_time: RwSignal::new(0.0),
+            playback_rate: RwSignal::new(1.0),
+            duration: RwSignal::new(None),
+            synced_time: StoredValue::new(0.0),
+        }
+    }
+
+    /// Duration of the video in seconds, `None` until its metadata is loaded.
+    pub fn duration(&self) -> Signal<Option<f64>> {
+        self.duration.read_only().into()
+    }
+
+    fn apply_playing(&self, video: &HtmlVideoElement) {
+        let playing = self.playing.get_untracked();
+        if playing && video.paused() {
+            self.play(video);
+        } else if !playing && !video.paused() {
+            let _ = video.pause();
+        }
+    }
+
+    fn apply_current_time(&self, video: &HtmlVideoElement) {
+        let current_time = self.current_time.get_untracked();
+        if self.synced_time.get_value() != current_time {
+            self.synced_time.set_value(current_time);
+            video.set_current_time(current_time);
+        }
+    }
+
+    fn apply_playback_rate(&self, video: &HtmlVideoElement) {
+        let playback_rate = self.playback_rate.get_untracked();
+        if video.playback_rate() != playback_rate {
+            video.set_playback_rate(playback_rate);
+        }
+    }
+
+    fn play(&self, video: &HtmlVideoElement) {
+        let Ok(promise) = video.play() else {
+            return;
+        };
+        // Playing is rejected without user interaction on most browsers
+        let playing = self.playing;
+        let on_rejected = Closure::once_into_js(move |_: JsValue| {
+            playing.try_set(false);
+        });
+        if let Some(catch) = Reflect::get(&promise, &JsValue::from_str("catch"))
+            .ok()
+            .and_then(|catch| catch.dyn_into::<Function>().ok())
+        {
+            let _ = catch.call1(&promise, &on_rejected);
+        }
+    }
+
+    /// Updates the signals from the video element.
+    fn sync_from(&self, video: &HtmlVideoElement) {
+        let playing = !video.paused();
+        if self.playing.try_get_untracked() != Some(playing) {
+            self.playing.try_set(playing);
+        }
+        let current_time = video.current_time();
+        self.synced_time.try_set_value(current_time);
+        if self.current_time.try_get_untracked() != Some(current_time) {
+            self.current_time.try_set(current_time);
+        }
+        let playback_rate = video.playback_rate();
+        if self.playback_rate.try_get_untracked() != Some(playback_rate) {
+            self.playback_rate.try_set(playback_rate);
+        }
+        let duration = Some(video.duration()).filter(|duration| duration.is_finite());
+        if self.duration.try_get_untracked() != Some(duration) {
+            self.duration.try_set(duration);
+        }
+    }
+}
+
+impl Default for VideoPlayback {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Gets the video element of the overlay, once it was added to a map.
+fn video_element(overlay: &leaflet::VideoOverlay) -> Option<HtmlVideoElement> {
+    let get_element = Reflect::get(overlay, &JsValue::from_str("getElement"))
+        .ok()?
+        .dyn_into::<Function>()
+        .ok()?;
+    get_element.call0(overlay).ok()?.dyn_into().ok()
+}
+
+/// Applies the playback to the video, and listens to it for the playback and the events.
+fn attach_video(
+    video: &HtmlVideoElement,
+    playback: Option<VideoPlayback>,
+    events: &VideoEvents,
+) -> Vec<DomEventListener> {
+    let mut listeners = Vec::new();
+    if let Some(playback) = playback {
+        playback.apply_current_time(video);
+        playback.apply_playback_rate(video);
+        playback.apply_playing(video);
+        let synced_video = video.clone();
+        listeners.push(DomEventListener::new(
+            video,
+            "play pause ended timeupdate seeked ratechange durationchange loadedmetadata",
+            move |_| playback.sync_from(&synced_video),
+        ));
+    }
+
+    let ended_events = events.clone();
+    let time_update_events = events.clone();
+    let time_update_video = video.clone();
+    let error_events = events.clone();
+    let error_video = video.clone();
+    listeners.extend([
+        DomEventListener::new(video, "ended", move |event| ended_events.ended(event)),
+        DomEventListener::new(video, "timeupdate", move |_| {
+            time_update_events.time_update(time_update_video.current_time());
+        }),
+        DomEventListener::new(video, "error", move |_| {
+            if let Some(error) = error_video.error() {
+                error_events.error(VideoError {
+                    code: error.code(),
+                    message: error.message(),
+                });
+            }
+        }),
+    ]);
+    listeners
+}
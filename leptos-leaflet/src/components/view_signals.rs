@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use leaflet::Map;
+use leptos::prelude::*;
+
+use super::map_container::map_loaded;
+use super::{use_leaflet_context, Bounds, Position};
+use crate::core::{JsStoredValue, LeafletEventListener};
+
+/// Controls how often the map view signals are updated.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ViewUpdateRate {
+    /// Update on every `moveend`/`zoomend` event.
+    #[default]
+    Immediate,
+    /// Update once no events were fired for the given duration.
+    Debounce(Duration),
+    /// Update at most once per the given duration, the last event is always applied.
+    Throttle(Duration),
+}
+
+/// Returns a signal with the center of the map, updated when the map stops moving.
+///
+/// # Panics
+///
+/// Panics if called outside of a `MapContainer`.
+pub fn use_map_center() -> Signal<Position> {
+    use_map_center_with_rate(ViewUpdateRate::Immediate)
+}
+
+/// Same as [`use_map_center`], with a custom update rate.
+pub fn use_map_center_with_rate(rate: ViewUpdateRate) -> Signal<Position> {
+    use_map_view(rate, |map| map.get_center().into())
+}
+
+/// Returns a signal with the zoom of the map, updated when the map stops zooming.
+///
+/// # Panics
+///
+/// Panics if called outside of a `MapContainer`.
+pub fn use_map_zoom() -> Signal<f64> {
+    use_map_zoom_with_rate(ViewUpdateRate::Immediate)
+}
+
+/// Same as [`use_map_zoom`], with a custom update rate.
+pub fn use_map_zoom_with_rate(rate: ViewUpdateRate) -> Signal<f64> {
+    use_map_view(rate, |map| map.get_zoom())
+}
+
+/// Returns a signal with the visible bounds of the map, updated when the map stops moving.
+///
+/// # Panics
+///
+/// Panics if called outside of a `MapContainer`.
+pub fn use_map_bounds() -> Signal<Bounds> {
+    use_map_bounds_with_rate(ViewUpdateRate::Immediate)
+}
+
+/// Same as [`use_map_bounds`], with a custom update rate.
+pub fn use_map_bounds_with_rate(rate: ViewUpdateRate) -> Signal<Bounds> {
+    use_map_view(rate, |map| map.get_bounds().into())
+}
+
+/// Creates a signal with a value read from the map on every `moveend`/`zoomend`.
+///
+/// The Leaflet listener is stored in the current scope, and removed when it is disposed.
+fn use_map_view<T>(rate: ViewUpdateRate, read: fn(&Map) -> T) -> Signal<T>
+where
+    T: Clone + Default + PartialEq + Send + Sync + 'static,
+{
+    let map_context =
+        use_leaflet_context().expect("map view hooks must be used within a MapContainer");
+    let value = RwSignal::new(T::default());
+    let listener = JsStoredValue::new_local(None::<LeafletEventListener>);
+    let timer = StoredValue::new(None::<TimeoutHandle>);
+    let last_update = StoredValue::new(0.0_f64);
+
+    Effect::new(move |_| {
+        let Some(map) = map_context.map() else {
+            return;
+        };
+        let update_map = map.clone();
+        let update = move || {
+            if map_loaded(&update_map) {
+                let new_value = read(&update_map);
+                if value
+                    .try_get_untracked()
+                    .is_some_and(|current| current != new_value)
+                {
+                    value.try_set(new_value);
+                }
+            }
+        };
+        update();
+
+        let handler = move |_: leaflet::Event| match rate {
+            ViewUpdateRate::Immediate => update(),
+            ViewUpdateRate::Debounce(delay) => {
+                if let Some(handle) = timer.get_value() {
+                    handle.clear();
+                }
+                let update = update.clone();
+                let handle = set_timeout_with_handle(
+                    move || {
+                        timer.set_value(None);
+                        update();
+                    },
+                    delay,
+                );
+                timer.set_value(handle.ok());
+            }
+            ViewUpdateRate::Throttle(interval) => {
+                let now = js_sys::Date::now();
+                let remaining = interval.as_millis() as f64 - (now - last_update.get_value());
+                if remaining <= 0.0 {
+                    last_update.set_value(now);
+                    update();
+                } else if timer.get_value().is_none() {
+                    // Trailing update, so the last position is never lost
+                    let update = update.clone();
+                    let handle = set_timeout_with_handle(
+                        move || {
+                            timer.set_value(None);
+                            last_update.set_value(js_sys::Date::now());
+                            update();
+                        },
+                        Duration::from_millis(remaining as u64),
+                    );
+                    timer.set_value(handle.ok());
+                }
+            }
+        };
+        listener.set_value(Some(LeafletEventListener::new(
+            &map,
+            "moveend zoomend",
+            handler,
+        )));
+    });
+
+    on_cleanup(move || {
+        if let Some(handle) = timer.try_get_value().flatten() {
+            handle.clear();
+        }
+        listener.try_update_value(|listener| *listener = None);
+    });
+
+    value.into()
+}
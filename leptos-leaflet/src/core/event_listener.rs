@@ -0,0 +1,95 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// A Leaflet event listener that is unregistered from its target when dropped.
+///
+/// The `on_*` helpers from `leaflet` can't be removed once set, this keeps the closure around so
+/// the listener lives exactly as long as this value. Storing it in a `JsStoredValue` ties the
+/// listener to the owning reactive scope.
+pub struct LeafletEventListener {
+    target: leaflet::Evented,
+    kind: String,
+    callback: Closure<dyn Fn(JsValue)>,
+}
+
+impl LeafletEventListener {
+    /// Registers `handler` on `target` for the event types in `kind`.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Any Leaflet object that extends `Evented`, like the map or a layer.
+    /// * `kind` - One or more space separated event types, e.g. `"moveend zoomend"`.
+    /// * `handler` - Callback receiving the event casted to `E`.
+    pub fn new<E>(target: &impl JsCast, kind: &str, handler: impl Fn(E) + 'static) -> Self
+    where
+        E: JsCast,
+    {
+        let target = target.unchecked_ref::<leaflet::Evented>().clone();
+        let callback = Closure::<dyn Fn(JsValue)>::new(move |event: JsValue| {
+            handler(event.unchecked_into::<E>());
+        });
+        target.on(kind, callback.as_ref());
+        Self {
+            target,
+            kind: kind.to_string(),
+            callback,
+        }
+    }
+}
+
+impl Drop for LeafletEventListener {
+    fn drop(&mut self) {
+        self.target.off(&self.kind, self.callback.as_ref());
+    }
+}
+
+/// A DOM event listener that is removed from its target when dropped.
+///
+/// The DOM counterpart of [`LeafletEventListener`], for elements created by Leaflet like the video
+/// of a `VideoOverlay`.
+pub struct DomEventListener {
+    target: web_sys::EventTarget,
+    kinds: Vec<String>,
+    callback: Closure<dyn Fn(web_sys::Event)>,
+}
+
+impl DomEventListener {
+    /// Registers `handler` on `target` for the event types in `kind`.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Any DOM event target, like an element or the document.
+    /// * `kind` - One or more space separated event types, e.g. `"play pause"`.
+    /// * `handler` - Callback receiving the event.
+    pub fn new(
+        target: &impl AsRef<web_sys::EventTarget>,
+        kind: &str,
+        handler: impl Fn(web_sys::Event) + 'static,
+    ) -> Self {
+        let target = target.as_ref().clone();
+        let callback = Closure::<dyn Fn(web_sys::Event)>::new(handler);
+        let kinds = kind
+            .split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        for kind in &kinds {
+            let _ =
+                target.add_event_listener_with_callback(kind, callback.as_ref().unchecked_ref());
+        }
+        Self {
+            target,
+            kinds,
+            callback,
+        }
+    }
+}
+
+impl Drop for DomEventListener {
+    fn drop(&mut self) {
+        for kind in &self.kinds {
+            let _ = self
+                .target
+                .remove_event_listener_with_callback(kind, self.callback.as_ref().unchecked_ref());
+        }
+    }
+}
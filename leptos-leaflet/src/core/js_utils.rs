@@ -0,0 +1,30 @@
+use js_sys::{Array, Function, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Calls the method `name` of a Leaflet object, `None` if it's missing or throws.
+pub(crate) fn call_method(target: &JsValue, name: &str, args: &[JsValue]) -> Option<JsValue> {
+    let method = get_function(target, name)?;
+    let args = args.iter().collect::<Array>();
+    method.apply(target, &args).ok()
+}
+
+/// Gets the function `name` of a JS object, `None` if it's missing.
+pub(crate) fn get_function(target: &JsValue, name: &str) -> Option<Function> {
+    Reflect::get(target, &JsValue::from_str(name))
+        .ok()?
+        .dyn_into::<Function>()
+        .ok()
+}
+
+/// Sets the option `key` of a Leaflet options object.
+pub(crate) fn set_option(options: &JsValue, key: &str, value: &JsValue) {
+    let _ = Reflect::set(options, &JsValue::from_str(key), value);
+}
+
+/// Returns the global Leaflet namespace `L`, `None` if Leaflet is not loaded.
+pub(crate) fn leaflet_namespace() -> Option<JsValue> {
+    Reflect::get(&js_sys::global(), &JsValue::from_str("L"))
+        .ok()
+        .filter(|leaflet| !leaflet.is_undefined())
+}
@@ -1,9 +1,13 @@
 /*
  * Copyright (c) HeadlessStudio  2023.
  */
+mod event_listener;
+mod js_utils;
 mod js_signals;
 mod thread_safe_jsvalue;
 
+pub use event_listener::{DomEventListener, LeafletEventListener};
+pub(crate) use js_utils::{call_method, get_function, leaflet_namespace, set_option};
 pub use js_signals::*;
 pub use thread_safe_jsvalue::{ThreadSafeJsValue, IntoThreadSafeJsValue};
 
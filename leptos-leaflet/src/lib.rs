@@ -6,14 +6,24 @@
 //!
 //! - [`MapContainer`](crate::MapContainer): A container for the Leaflet map. Where all the other components are added.
 //! - [`Circle`](crate::Circle): A circle overlay that represents a circle on the map.
+//! - [`CircleMarker`](crate::CircleMarker): A circle with a radius in pixels, that keeps its size at any zoom.
 //! - [`Control`](crate::Control): A control that represents a control on the map.
+//! - [`DistortableImageOverlay`](crate::DistortableImageOverlay): An image overlay placed by three or four corners.
+//! - [`DrawControl`](crate::DrawControl): A toolbar to draw markers, lines, polygons, rectangles and circles.
+//! - [`FeatureGroup`](crate::FeatureGroup): A layer group with shared events, style and bounds.
+//! - [`GeoJson`](crate::GeoJson): Renders GeoJSON data, with a layer per feature.
 //! - [`ImageOverlay`](crate::ImageOverlay): An image overlay that represents an image on the map.
+//! - [`LayersControl`](crate::LayersControl): A control to switch base layers and toggle overlays,
+//!   declared with [`BaseLayer`](crate::BaseLayer) and [`Overlay`](crate::Overlay) children.
+//! - [`LayerGroup`](crate::LayerGroup): A group of layers that can be shown and hidden together.
 //! - [`Marker`](crate::Marker): A marker overlay that represents a marker on the map.
 //! - [`Pane`](crate::Pane): A custom map pane for organizing layers with custom z-index ordering.
 //! - [`Polygon`](crate::Polygon): A polygon overlay that represents a polygon on the map.
 //! - [`Polyline`](crate::Polyline): A polyline overlay that represents a polyline on the map.
 //! - [`Popup`](crate::Popup): A popup overlay that represents a popup on the map.
 //! - [`QuadTileLayer`](crate::QuadTileLayer): A tile layer that uses quadkey-based URLs instead of x/y/z coordinates.
+//! - [`Rectangle`](crate::Rectangle): A rectangle overlay that represents a rectangle on the map.
+//! - [`SvgOverlay`](crate::SvgOverlay): An SVG overlay that stretches Leptos SVG views over bounds.
 //! - [`TileLayer`](crate::TileLayer): A tile layer that represents a tile layer on the map.
 //! - [`TileLayerWms`](crate::TileLayerWms): A tile layer that represents a tile layer on the map.
 //! - [`Tooltip`](crate::Tooltip): A tooltip overlay that represents a tooltip on the map.
@@ -24,7 +34,11 @@
 //!
 //! - [`IntoLatLng`](crate::IntoLatLng): A trait to convert types into `leaflet::LatLng` instances.
 //! - [`LeafletMapContext`](crate::LeafletMapContext): A context struct for the Leaflet map.
+//! - [`DrawHandle`](crate::DrawHandle): A handle to drive a `DrawControl`, returned by [`use_draw`](crate::use_draw).
+//! - [`MapHandle`](crate::MapHandle): A typed handle to the map, returned by [`use_map`](crate::use_map).
 //! - [`Position`](crate::Position): A struct to represent a position on the map.
+//! - [`use_map_center`](crate::use_map_center), [`use_map_zoom`](crate::use_map_zoom) and
+//!   [`use_map_bounds`](crate::use_map_bounds): Signals following the view of the map.
 //!
 //! ## Example
 //!
@@ -92,4 +106,7 @@ pub mod prelude {
 /// Leaflet re-exports
 pub use leaflet;
 
+/// GeoJSON re-exports, for the types used by [`GeoJson`](crate::GeoJson)
+pub use geojson;
+
 use paste::paste;